@@ -0,0 +1,181 @@
+//! Pool-level statistics that don't depend on a particular matching.
+
+use std::collections::HashMap;
+
+use crate::error::NemesisError;
+use crate::scoring::{marginal_contribution, ScoringStrategy, SimpleDifferenceScorer};
+use crate::user::{User, MAX_RESPONSE, MIN_RESPONSE};
+
+/// Mean answer for each question across the pool.
+///
+/// Users with fewer answers than the longest response vector simply don't
+/// contribute to the missing positions.
+pub fn question_means(users: &[User]) -> Vec<f64> {
+    let num_questions = users.iter().map(User::num_questions).max().unwrap_or(0);
+    let mut sums = vec![0.0; num_questions];
+    let mut counts = vec![0usize; num_questions];
+    for user in users {
+        for (q, &r) in user.responses.iter().enumerate() {
+            sums[q] += r as f64;
+            counts[q] += 1;
+        }
+    }
+    sums.iter()
+        .zip(&counts)
+        .map(|(&s, &c)| if c == 0 { 0.0 } else { s / c as f64 })
+        .collect()
+}
+
+/// How polarizing each user is relative to the rest of the pool.
+///
+/// A user's controversy is the sum, over every question, of the absolute
+/// distance between their answer and the pool's mean answer to that question:
+/// `Σ_q |r_q - mean_q|`. Users who sit at the consensus score 0; users who
+/// take the far end of every debate score highest.
+pub fn controversy_scores(users: &[User]) -> HashMap<String, f64> {
+    let means = question_means(users);
+    users
+        .iter()
+        .map(|user| {
+            let score = user
+                .responses
+                .iter()
+                .zip(&means)
+                .map(|(&r, &m)| (r as f64 - m).abs())
+                .sum();
+            (user.id.clone(), score)
+        })
+        .collect()
+}
+
+/// Index of the candidate question that would most increase pool diversity.
+///
+/// `candidate_responses[c][i]` is user `i`'s hypothetical answer to candidate
+/// `c`. Every pair's opposition is its [`SimpleDifferenceScorer`] score on
+/// the existing answers, and adding a candidate adds the pair's gap on it. The
+/// candidate that raises the variance of those pairwise scores the most wins:
+/// a question that deepens the pool's existing divides beats one that cuts
+/// across them, and one everyone agrees on adds nothing. Ties go to the
+/// earlier candidate; with no candidates there is nothing to recommend.
+/// Errors with [`NemesisError::MismatchedLengths`] if a candidate doesn't
+/// have exactly one answer per pool member.
+pub fn most_divisive_candidate(
+    pool_existing: &[User],
+    candidate_responses: &[Vec<i32>],
+) -> Result<Option<usize>, NemesisError> {
+    let n = pool_existing.len();
+    if let Some(answers) = candidate_responses.iter().find(|a| a.len() != n) {
+        return Err(NemesisError::MismatchedLengths {
+            expected: n,
+            got: answers.len(),
+        });
+    }
+    let existing: Vec<(usize, usize, f64)> = (0..n)
+        .flat_map(|i| ((i + 1)..n).map(move |j| (i, j)))
+        .map(|(i, j)| {
+            let score =
+                SimpleDifferenceScorer.calculate_score(&pool_existing[i], &pool_existing[j]);
+            (i, j, score)
+        })
+        .collect();
+    let baseline = variance(existing.iter().map(|&(_, _, s)| s));
+
+    let mut best: Option<(usize, f64)> = None;
+    for (index, answers) in candidate_responses.iter().enumerate() {
+        let added = variance(
+            existing
+                .iter()
+                .map(|&(i, j, s)| s + (answers[i] as f64 - answers[j] as f64).abs()),
+        ) - baseline;
+        if best.is_none_or(|(_, top)| added > top) {
+            best = Some((index, added));
+        }
+    }
+    Ok(best.map(|(index, _)| index))
+}
+
+/// Population variance, or 0 for no values.
+fn variance(values: impl Iterator<Item = f64> + Clone) -> f64 {
+    let count = values.clone().count();
+    if count == 0 {
+        return 0.0;
+    }
+    let mean = values.clone().sum::<f64>() / count as f64;
+    values.map(|v| (v - mean).powi(2)).sum::<f64>() / count as f64
+}
+
+/// Average opposition contributed by each answer value, indexed 1 to 7.
+///
+/// For every pair of users and every question they both answered, the
+/// question's [`marginal_contribution`] to the pair's score is credited to
+/// both answers given. Entry `v` is the mean credit per occurrence of answer
+/// `v`, so a high value means that answer tends to put its holder at odds
+/// with the pool. Index 0, and any value nobody chose, is 0; out-of-scale
+/// answers are ignored. Errors with [`NemesisError::NotDecomposable`] unless
+/// the scorer is decomposable.
+pub fn value_opposition_profile<S: ScoringStrategy>(
+    users: &[User],
+    scorer: &S,
+) -> Result<[f64; 8], NemesisError> {
+    let mut sums = [0.0; 8];
+    let mut counts = [0usize; 8];
+    for (i, u1) in users.iter().enumerate() {
+        for u2 in &users[i + 1..] {
+            for (q, (&a, &b)) in u1.responses.iter().zip(&u2.responses).enumerate() {
+                let contribution = marginal_contribution(scorer, u1, u2, q)?;
+                for value in [a, b] {
+                    if (MIN_RESPONSE..=MAX_RESPONSE).contains(&value) {
+                        sums[value as usize] += contribution;
+                        counts[value as usize] += 1;
+                    }
+                }
+            }
+        }
+    }
+    let mut profile = [0.0; 8];
+    for (mean, (&sum, &count)) in profile.iter_mut().zip(sums.iter().zip(&counts)) {
+        if count > 0 {
+            *mean = sum / count as f64;
+        }
+    }
+    Ok(profile)
+}
+
+/// Pairs of users so alike they may be the same person submitting twice.
+///
+/// Returns the ids of every pair scoring strictly below `max_distance`
+/// under `scorer`, in pool order, for a human to review; nothing is
+/// removed. Scorers where a low score means similar answers (simple
+/// difference, Euclidean) suit this best.
+pub fn find_near_duplicates<S: ScoringStrategy>(
+    users: &[User],
+    max_distance: f64,
+    scorer: &S,
+) -> Vec<(String, String)> {
+    let mut suspects = Vec::new();
+    for (i, u1) in users.iter().enumerate() {
+        for u2 in &users[i + 1..] {
+            if scorer.calculate_score(u1, u2) < max_distance {
+                suspects.push((u1.id.clone(), u2.id.clone()));
+            }
+        }
+    }
+    suspects
+}
+
+/// How opposed `user` is, on average, to a randomly chosen pool member.
+///
+/// The mean score of `user` against everyone in `pool` except themselves
+/// (matched by id, so `user` may or may not be in `pool`). A "how contrarian
+/// are you" figure; 0 when there is nobody else to compare against.
+pub fn expected_opposition<S: ScoringStrategy>(user: &User, pool: &[User], scorer: &S) -> f64 {
+    let scores: Vec<f64> = pool
+        .iter()
+        .filter(|other| other.id != user.id)
+        .map(|other| scorer.calculate_score(user, other))
+        .collect();
+    if scores.is_empty() {
+        return 0.0;
+    }
+    scores.iter().sum::<f64>() / scores.len() as f64
+}
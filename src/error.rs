@@ -0,0 +1,141 @@
+use std::fmt;
+
+/// Errors produced while validating users or configuring scorers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NemesisError {
+    /// A response fell outside the `MIN_RESPONSE..=MAX_RESPONSE` scale.
+    InvalidResponse { index: usize, value: i32 },
+    /// An answer confidence was outside `0.0..=1.0` (or NaN).
+    InvalidConfidence { index: usize, value: f64 },
+    /// A response scale whose minimum isn't below its maximum.
+    InvalidScale { min: i32, max: i32 },
+    /// A user id that is empty or only whitespace.
+    InvalidId(String),
+    /// A question was skipped where a complete answer sheet is required.
+    MissingResponse { index: usize },
+    /// A user submitted no responses at all.
+    EmptyResponses,
+    /// Two response vectors (or a response vector and a weight vector) differ in length.
+    MismatchedLengths { expected: usize, got: usize },
+    /// A scorer was configured with unusable weights.
+    InvalidWeights(String),
+    /// A problem with one specific user in a pool.
+    InvalidUser {
+        id: String,
+        error: Box<NemesisError>,
+    },
+    /// Fewer users were given than the matcher's minimum pool size.
+    PoolTooSmall { got: usize, min: usize },
+    /// A pool larger than an exponential-time routine accepts.
+    PoolTooLarge { got: usize, max: usize },
+    /// A matching leaves out more users than the single leftover an odd
+    /// pool allows.
+    IncompleteMatching { unmatched: Vec<String> },
+    /// An id was referenced that isn't in the pool.
+    UnknownUserId(String),
+    /// An id appeared more than once where it must be unique.
+    DuplicateUserId(String),
+    /// A stored payload could not be parsed.
+    Deserialization(String),
+    /// A stored payload was written by a newer, unknown schema version.
+    UnsupportedSchemaVersion(u32),
+    /// A per-question breakdown was requested from a scorer whose score isn't
+    /// a sum of per-question terms.
+    NotDecomposable(String),
+    /// A scorer that weighs questions by position was given to a wrapper
+    /// that drops questions, which would shift it onto the wrong ones.
+    PositionDependent(String),
+    /// A scored pair referred to a user index past the end of the pool.
+    PairIndexOutOfBounds { index: usize, len: usize },
+    /// A scorer produced a score above its own theoretical maximum, which
+    /// means answers off the scale or an inconsistent scorer.
+    ScoreOutOfBounds {
+        user1_id: String,
+        user2_id: String,
+        score: f64,
+        max: f64,
+    },
+    /// A scorer produced NaN or an infinite score for a pair.
+    NonFiniteScore {
+        user1_id: String,
+        user2_id: String,
+        score: f64,
+    },
+}
+
+impl fmt::Display for NemesisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NemesisError::InvalidResponse { index, value } => {
+                write!(
+                    f,
+                    "response {value} at index {index} is outside the valid scale"
+                )
+            }
+            NemesisError::InvalidConfidence { index, value } => {
+                write!(
+                    f,
+                    "confidence {value} at index {index} must be between 0 and 1"
+                )
+            }
+            NemesisError::InvalidScale { min, max } => {
+                write!(f, "scale minimum {min} must be below maximum {max}")
+            }
+            NemesisError::InvalidId(id) => write!(f, "user id {id:?} is blank"),
+            NemesisError::MissingResponse { index } => {
+                write!(f, "question {index} was not answered")
+            }
+            NemesisError::EmptyResponses => write!(f, "user has no responses"),
+            NemesisError::MismatchedLengths { expected, got } => {
+                write!(f, "expected {expected} responses, got {got}")
+            }
+            NemesisError::InvalidWeights(reason) => write!(f, "invalid weights: {reason}"),
+            NemesisError::InvalidUser { id, error } => write!(f, "user {id:?}: {error}"),
+            NemesisError::PoolTooSmall { got, min } => {
+                write!(f, "need at least {min} users to match, got {got}")
+            }
+            NemesisError::PoolTooLarge { got, max } => {
+                write!(f, "pool of {got} users exceeds the limit of {max}")
+            }
+            NemesisError::IncompleteMatching { unmatched } => {
+                write!(f, "matching leaves out {unmatched:?}")
+            }
+            NemesisError::UnknownUserId(id) => write!(f, "no user with id {id:?}"),
+            NemesisError::DuplicateUserId(id) => write!(f, "user id {id:?} used more than once"),
+            NemesisError::Deserialization(reason) => {
+                write!(f, "could not deserialize payload: {reason}")
+            }
+            NemesisError::UnsupportedSchemaVersion(version) => {
+                write!(f, "unsupported schema version {version}")
+            }
+            NemesisError::NotDecomposable(name) => {
+                write!(f, "scorer {name} is not a sum of per-question terms")
+            }
+            NemesisError::PositionDependent(name) => {
+                write!(f, "scorer {name} depends on question positions")
+            }
+            NemesisError::PairIndexOutOfBounds { index, len } => {
+                write!(f, "pair refers to user index {index} in a pool of {len}")
+            }
+            NemesisError::ScoreOutOfBounds {
+                user1_id,
+                user2_id,
+                score,
+                max,
+            } => write!(
+                f,
+                "score {score} for users {user1_id:?} and {user2_id:?} exceeds the maximum {max}"
+            ),
+            NemesisError::NonFiniteScore {
+                user1_id,
+                user2_id,
+                score,
+            } => write!(
+                f,
+                "non-finite score {score} for users {user1_id:?} and {user2_id:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NemesisError {}
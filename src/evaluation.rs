@@ -0,0 +1,350 @@
+//! Measures of how good a matching is.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::matcher::{ordered_pair, Match, NemesisMatcher};
+use crate::rng::SplitMix64;
+use crate::scoring::ScoringStrategy;
+use crate::user::{User, MAX_RESPONSE, MIN_RESPONSE};
+
+/// Sum of the scores of `matches`.
+pub fn total_score(matches: &[Match]) -> f64 {
+    matches.iter().map(|m| m.score).sum()
+}
+
+/// How much total opposition greedy leaves on the table, as a fraction of optimal.
+///
+/// Computes `(optimal_total - greedy_total) / optimal_total` by running both
+/// matchers, so it costs an optimal run. 0 means greedy was optimal on this
+/// pool; a pool with no opposition at all also reports 0.
+pub fn optimality_gap<S: ScoringStrategy>(users: &[User], scorer: &S) -> f64 {
+    let matcher = NemesisMatcher::new(scorer);
+    let optimal = total_score(&matcher.find_matches_optimal(users));
+    if optimal <= 0.0 {
+        return 0.0;
+    }
+    let greedy = total_score(&matcher.find_matches_lenient(users));
+    (optimal - greedy) / optimal
+}
+
+/// Spearman rank correlation between how `a` and `b` order every pair in `pool`.
+///
+/// 1.0 means switching between the two strategies can't change which pairs
+/// look most opposed; values near 0 mean the orderings are unrelated. Tied
+/// scores share their average rank. When either scorer gives every pair the
+/// same score the correlation is undefined, and this reports 1.0 if both do
+/// (nothing to disagree on) and 0.0 otherwise.
+pub fn rank_correlation(pool: &[User], a: &dyn ScoringStrategy, b: &dyn ScoringStrategy) -> f64 {
+    let mut scores_a = Vec::new();
+    let mut scores_b = Vec::new();
+    for i in 0..pool.len() {
+        for j in (i + 1)..pool.len() {
+            scores_a.push(a.calculate_score(&pool[i], &pool[j]));
+            scores_b.push(b.calculate_score(&pool[i], &pool[j]));
+        }
+    }
+    let (ranks_a, ranks_b) = (average_ranks(&scores_a), average_ranks(&scores_b));
+
+    let n = ranks_a.len() as f64;
+    let mean = (n + 1.0) / 2.0;
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (ra, rb) in ranks_a.iter().zip(&ranks_b) {
+        cov += (ra - mean) * (rb - mean);
+        var_a += (ra - mean).powi(2);
+        var_b += (rb - mean).powi(2);
+    }
+    match (var_a == 0.0, var_b == 0.0) {
+        (true, true) => 1.0,
+        (true, false) | (false, true) => 0.0,
+        (false, false) => cov / (var_a * var_b).sqrt(),
+    }
+}
+
+/// 1-based ranks of `values`, with ties sharing the mean of their positions.
+fn average_ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&x, &y| values[x].total_cmp(&values[y]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &index in &order[start..end] {
+            ranks[index] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// How much more opposed the matched pairs are than random pairs would be.
+///
+/// The ratio of the mean score of `matches` (rescored with `scorer`) to the
+/// mean score over every pair in `users`, which is what a uniformly random
+/// matching scores on average. 1.0 means the matching is no better than
+/// chance; the further above 1.0, the more reliably opposites were paired.
+/// Matches naming unknown ids are skipped, and the statistic is 0 when there
+/// are no scorable matches or the pool has no opposition at all.
+pub fn matching_assortativity<S: ScoringStrategy>(
+    matches: &[Match],
+    users: &[User],
+    scorer: &S,
+) -> f64 {
+    let by_id: HashMap<&str, &User> = users.iter().map(|u| (u.id.as_str(), u)).collect();
+    let matched: Vec<f64> = matches
+        .iter()
+        .filter_map(|m| {
+            let u1 = by_id.get(m.user1_id.as_str())?;
+            let u2 = by_id.get(m.user2_id.as_str())?;
+            Some(scorer.calculate_score(u1, u2))
+        })
+        .collect();
+    let baseline = NemesisMatcher::new(scorer).pool_diversity(users);
+    if matched.is_empty() || baseline <= 0.0 {
+        return 0.0;
+    }
+    matched.iter().sum::<f64>() / matched.len() as f64 / baseline
+}
+
+/// Mean absolute answer gap per question over just the matched pairs.
+///
+/// Ranks the topics that produced the most clash in an actual run, as
+/// opposed to [`controversy_scores`](crate::controversy_scores), which looks
+/// at the whole pool. Matches naming unknown ids are skipped; a question no
+/// scorable pair both answered reports 0.
+pub fn matched_question_opposition(matches: &[Match], users: &[User]) -> Vec<f64> {
+    let by_id: HashMap<&str, &User> = users.iter().map(|u| (u.id.as_str(), u)).collect();
+    let mut sums: Vec<f64> = Vec::new();
+    let mut counts: Vec<usize> = Vec::new();
+    for m in matches {
+        let (Some(u1), Some(u2)) = (
+            by_id.get(m.user1_id.as_str()),
+            by_id.get(m.user2_id.as_str()),
+        ) else {
+            continue;
+        };
+        let shared = u1.num_questions().min(u2.num_questions());
+        if sums.len() < shared {
+            sums.resize(shared, 0.0);
+            counts.resize(shared, 0);
+        }
+        for (q, (&a, &b)) in u1.responses.iter().zip(&u2.responses).enumerate() {
+            sums[q] += (a as f64 - b as f64).abs();
+            counts[q] += 1;
+        }
+    }
+    sums.iter()
+        .zip(&counts)
+        .map(|(&s, &c)| if c == 0 { 0.0 } else { s / c as f64 })
+        .collect()
+}
+
+/// Pair-level churn between two matchings, from [`matching_diff`].
+///
+/// Each pair is its two ids with the smaller first, so orientation changes
+/// don't count as churn.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MatchingDiff {
+    /// Pairs present in both matchings, in `old` order.
+    pub stayed: Vec<(String, String)>,
+    /// Pairs in `old` that `new` no longer has, in `old` order.
+    pub broken: Vec<(String, String)>,
+    /// Pairs in `new` that `old` didn't have, in `new` order.
+    pub formed: Vec<(String, String)>,
+}
+
+impl MatchingDiff {
+    /// Every id in a broken or newly formed pair: the users to notify.
+    pub fn affected_ids(&self) -> HashSet<&str> {
+        self.broken
+            .iter()
+            .chain(&self.formed)
+            .flat_map(|(a, b)| [a.as_str(), b.as_str()])
+            .collect()
+    }
+}
+
+/// Compares a re-run matching against the previous one.
+pub fn matching_diff(old: &[Match], new: &[Match]) -> MatchingDiff {
+    let key = |m: &Match| ordered_pair(m.user1_id.clone(), m.user2_id.clone());
+    let old_pairs: HashSet<(String, String)> = old.iter().map(key).collect();
+    let new_pairs: HashSet<(String, String)> = new.iter().map(key).collect();
+    let (stayed, broken) = old.iter().map(key).partition(|p| new_pairs.contains(p));
+    MatchingDiff {
+        stayed,
+        broken,
+        formed: new
+            .iter()
+            .map(key)
+            .filter(|p| !old_pairs.contains(p))
+            .collect(),
+    }
+}
+
+/// How much admitting `candidate` would raise the pool's best total
+/// opposition.
+///
+/// The difference between the optimal matching's total score with the
+/// candidate added and without. It can be 0 (they would only displace
+/// someone into the leftover spot) but never negative, since the old
+/// matching is still available.
+pub fn marginal_user_value<S: ScoringStrategy>(pool: &[User], candidate: &User, scorer: &S) -> f64 {
+    let matcher = NemesisMatcher::new(scorer);
+    let without = total_score(&matcher.find_matches_optimal(pool));
+    let mut with_candidate = pool.to_vec();
+    with_candidate.push(candidate.clone());
+    total_score(&matcher.find_matches_optimal(&with_candidate)) - without
+}
+
+/// How many distinct questions some matched pair clashes strongly on.
+///
+/// A question counts once at least one matched pair's answers differ by
+/// `strong_threshold` or more, so the result measures topic variety across
+/// the whole event rather than the depth of any one clash. Matches naming
+/// unknown ids are skipped.
+pub fn topic_coverage(matches: &[Match], users: &[User], strong_threshold: i32) -> usize {
+    let by_id: HashMap<&str, &User> = users.iter().map(|u| (u.id.as_str(), u)).collect();
+    let mut covered: HashSet<usize> = HashSet::new();
+    for m in matches {
+        let (Some(u1), Some(u2)) = (
+            by_id.get(m.user1_id.as_str()),
+            by_id.get(m.user2_id.as_str()),
+        ) else {
+            continue;
+        };
+        for (q, (&a, &b)) in u1.responses.iter().zip(&u2.responses).enumerate() {
+            if (a as i64 - b as i64).abs() >= strong_threshold as i64 {
+                covered.insert(q);
+            }
+        }
+    }
+    covered.len()
+}
+
+/// Runs greedy matching under each strategy and keeps the most opposed result.
+///
+/// Raw totals aren't comparable across strategies (cosine tops out at 2 per
+/// pair, simple difference at 6 per question), so each match score is divided
+/// by the strategy's [`score_bounds`](ScoringStrategy::score_bounds) maximum
+/// for the pool's question count, putting every strategy on a 0-1 scale. A
+/// strategy without a finite bound is measured with
+/// [`normalized_per_question`](ScoringStrategy::normalized_per_question)
+/// instead, which is only roughly comparable. Returns the winner's name and
+/// matches; ties go to the earlier strategy, and an empty list returns an
+/// empty name and no matches.
+pub fn best_strategy_matching(
+    users: &[User],
+    strategies: Vec<Box<dyn ScoringStrategy>>,
+) -> (String, Vec<Match>) {
+    let num_questions = users.iter().map(User::num_questions).max().unwrap_or(0);
+    let by_id: HashMap<&str, &User> = users.iter().map(|u| (u.id.as_str(), u)).collect();
+    let mut best: Option<(f64, String, Vec<Match>)> = None;
+    for strategy in strategies {
+        let matches = NemesisMatcher::new(&strategy).find_matches_lenient(users);
+        let (_, upper) = strategy.score_bounds(num_questions);
+        let normalized: f64 = if upper.is_finite() && upper > 0.0 {
+            total_score(&matches) / upper
+        } else {
+            matches
+                .iter()
+                .map(|m| {
+                    strategy.normalized_per_question(
+                        by_id[m.user1_id.as_str()],
+                        by_id[m.user2_id.as_str()],
+                    )
+                })
+                .sum()
+        };
+        if best.as_ref().is_none_or(|(top, _, _)| normalized > *top) {
+            best = Some((normalized, strategy.name(), matches));
+        }
+    }
+    best.map(|(_, name, matches)| (name, matches))
+        .unwrap_or_default()
+}
+
+/// Pairs of matches in an existing matching that would score higher with
+/// their partners swapped.
+///
+/// For matches `a–b` and `c–d`, the two ways to re-pair the four users
+/// (`a–c, b–d` and `a–d, b–c`) are rescored with `scorer`; if either beats
+/// the current pairs' combined score, the two current matches are reported.
+/// Useful for checking a hand-edited matching. Matches naming unknown ids are
+/// skipped, and results follow the order of `matches`.
+pub fn suggest_swaps<S: ScoringStrategy>(
+    matches: &[Match],
+    users: &[User],
+    scorer: &S,
+) -> Vec<(Match, Match)> {
+    let by_id: HashMap<&str, &User> = users.iter().map(|u| (u.id.as_str(), u)).collect();
+    let known: Vec<(&Match, &User, &User)> = matches
+        .iter()
+        .filter_map(|m| {
+            let u1 = by_id.get(m.user1_id.as_str())?;
+            let u2 = by_id.get(m.user2_id.as_str())?;
+            Some((m, *u1, *u2))
+        })
+        .collect();
+    let score = |x: &User, y: &User| scorer.calculate_score(x, y);
+
+    let mut swaps = Vec::new();
+    for (i, &(m1, a, b)) in known.iter().enumerate() {
+        for &(m2, c, d) in &known[i + 1..] {
+            let current = score(a, b) + score(c, d);
+            let swapped = (score(a, c) + score(b, d)).max(score(a, d) + score(b, c));
+            if swapped > current {
+                swaps.push((m1.clone(), m2.clone()));
+            }
+        }
+    }
+    swaps
+}
+
+/// How much of the greedy matching survives small changes of heart.
+///
+/// Each trial nudges every answer in the pool by -1, 0 or +1 at random
+/// (clamped to the scale) and rematches greedily with `scorer`. Returns the
+/// fraction of the original pairs that are matched again, averaged over
+/// `trials`; 1.0 means the matching never changed. The same `seed` gives the
+/// same result. A pool with no pairs, or zero trials, counts as fully stable.
+pub fn stability_under_noise<S: ScoringStrategy>(
+    users: &[User],
+    scorer: &S,
+    trials: usize,
+    seed: u64,
+) -> f64 {
+    let matcher = NemesisMatcher::new(scorer);
+    let pair_set = |matches: Vec<Match>| -> HashSet<(String, String)> {
+        matches
+            .into_iter()
+            .map(|m| ordered_pair(m.user1_id, m.user2_id))
+            .collect()
+    };
+    let baseline = pair_set(matcher.find_matches_lenient(users));
+    if baseline.is_empty() || trials == 0 {
+        return 1.0;
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    let mut kept = 0;
+    for _ in 0..trials {
+        let noisy: Vec<User> = users
+            .iter()
+            .map(|u| {
+                let responses = u
+                    .responses
+                    .iter()
+                    .map(|&r| (r + rng.range_i32(-1, 1)).clamp(MIN_RESPONSE, MAX_RESPONSE))
+                    .collect();
+                u.with_responses(responses)
+            })
+            .collect();
+        kept += pair_set(matcher.find_matches_lenient(&noisy))
+            .intersection(&baseline)
+            .count();
+    }
+    kept as f64 / (baseline.len() * trials) as f64
+}
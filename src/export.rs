@@ -0,0 +1,26 @@
+//! Rendering matchings for other tools.
+
+use crate::matcher::Match;
+
+/// Renders `matches` as an undirected GraphViz DOT graph, one edge per match
+/// labelled with its score.
+///
+/// Ids are always quoted, with backslashes and double quotes escaped, so any
+/// id produces valid DOT. Pipe the result into `dot -Tsvg` to draw it.
+pub fn matches_to_dot(matches: &[Match]) -> String {
+    let mut dot = String::from("graph {\n");
+    for m in matches {
+        dot.push_str(&format!(
+            "  {} -- {} [label=\"{}\"];\n",
+            quote(&m.user1_id),
+            quote(&m.user2_id),
+            m.score
+        ));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,33 @@
+//! Nemesis Finder: match people with the person whose opinions differ from theirs the most.
+//!
+//! Participants answer a questionnaire on a 1-7 scale ([`User`]). A
+//! [`ScoringStrategy`] turns a pair of answer sheets into an opposition score,
+//! and a [`NemesisMatcher`] uses those scores to pair everybody up.
+
+#[macro_use]
+mod logging;
+
+pub mod analysis;
+pub mod error;
+pub mod evaluation;
+pub mod export;
+pub mod matcher;
+pub mod packed;
+pub mod record;
+mod rng;
+pub mod scoring;
+#[cfg(feature = "test-util")]
+pub mod testing;
+pub mod user;
+
+pub use analysis::controversy_scores;
+pub use error::NemesisError;
+pub use matcher::{Constraints, Feasibility, Match, NemesisMatcher, TagConstraint};
+pub use scoring::{
+    CachedScorer, CapPerQuestion, CircularDifferenceScorer, ClampScorer, ConfidenceScorer,
+    CosineSimilarityScorer, EuclideanDistanceScorer, IgnoreMutualNeutral, ImportanceScorer,
+    InvertScorer, PolarizationScorer, RecordingScorer, Scorer, ScoringStrategy,
+    SharedConvictionScorer, SimpleDifferenceScorer, StrategyConfig, TableConvictionScorer,
+    WeightedCosineScorer, WeightedScorer, ZScoreScorer,
+};
+pub use user::{Scale, User};
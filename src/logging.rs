@@ -0,0 +1,14 @@
+//! Debug logging that compiles away unless the `log` feature is on.
+
+/// `log::debug!` under the `log` feature; otherwise the arguments are still
+/// type-checked but nothing is formatted or emitted.
+macro_rules! debug_log {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "log")]
+        log::debug!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    };
+}
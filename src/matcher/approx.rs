@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+
+use crate::matcher::{unmatched_users, Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::{User, NEUTRAL_RESPONSE};
+
+/// Greedy matching that only scores each user's `k` most promising candidates.
+///
+/// Candidates come from a cheap pre-filter: every user is reduced to the sum
+/// of their neutral-centred answers, and a user's candidates are the `k`
+/// users whose sums sit closest to the mirror image of their own (someone at
+/// `+9` looks for people near `-9`). Only those pairs are scored and fed to
+/// greedy selection; anyone left over is then matched exactly among
+/// themselves, so everybody still gets a partner.
+///
+/// This scores roughly `n * k` pairs instead of `n² / 2`, at the cost of
+/// accuracy: opposition that cancels out in the sum (`[1, 7]` vs `[7, 1]`) is
+/// invisible to the pre-filter. Larger `k` trades speed back for accuracy, and
+/// `k >= n - 1` is exactly greedy.
+#[derive(Debug, Clone)]
+pub struct ApproxMatcher<S: ScoringStrategy> {
+    matcher: NemesisMatcher<S>,
+    k: usize,
+}
+
+impl<S: ScoringStrategy> ApproxMatcher<S> {
+    pub fn new(scorer: S, k: usize) -> Self {
+        ApproxMatcher {
+            matcher: NemesisMatcher::new(scorer),
+            k,
+        }
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// The pruned candidate pairs, scored, as `(i, j, score)` with `i < j`.
+    pub fn candidate_pairs(&self, users: &[User]) -> Vec<(usize, usize, f64)> {
+        let sums: Vec<i64> = users
+            .iter()
+            .map(|u| {
+                u.responses
+                    .iter()
+                    .map(|&r| (r - NEUTRAL_RESPONSE) as i64)
+                    .sum()
+            })
+            .collect();
+        let mut by_sum: Vec<usize> = (0..users.len()).collect();
+        by_sum.sort_by_key(|&i| sums[i]);
+
+        let mut seen: HashSet<(usize, usize)> = HashSet::new();
+        let mut pairs = Vec::with_capacity(users.len() * self.k);
+        for i in 0..users.len() {
+            let target = -sums[i];
+            // Walk outwards from the mirror position, nearest sums first.
+            let mut right = by_sum.partition_point(|&j| sums[j] < target);
+            let mut left = right;
+            let mut taken = 0;
+            while taken < self.k && (left > 0 || right < by_sum.len()) {
+                let go_left = right >= by_sum.len()
+                    || (left > 0
+                        && (target - sums[by_sum[left - 1]]).abs()
+                            <= (sums[by_sum[right]] - target).abs());
+                let j = if go_left {
+                    left -= 1;
+                    by_sum[left]
+                } else {
+                    right += 1;
+                    by_sum[right - 1]
+                };
+                if j == i {
+                    continue;
+                }
+                taken += 1;
+                let key = (i.min(j), i.max(j));
+                if seen.insert(key) {
+                    let score = self.matcher.pair_score(&users[key.0], &users[key.1]);
+                    pairs.push((key.0, key.1, score));
+                }
+            }
+        }
+        // Index order, matching the layout of calculate_all_pairs.
+        pairs.sort_unstable_by_key(|&(i, j, _)| (i, j));
+        pairs
+    }
+
+    /// Approximate greedy matching over the pruned candidates.
+    pub fn find_matches(&self, users: &[User]) -> Vec<Match> {
+        let pairs = self.candidate_pairs(users);
+        let mut matches = self.matcher.greedy_select(users, pairs);
+        let leftovers: Vec<User> = unmatched_users(users, &matches)
+            .into_iter()
+            .cloned()
+            .collect();
+        matches.extend(self.matcher.find_matches_lenient(&leftovers));
+        matches
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::matcher::blossom::max_weight_matching;
+use crate::matcher::constraints::ordered_pair;
+use crate::matcher::{Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    /// Matches users across two groups only, never within a group.
+    ///
+    /// Maximises total opposition over all cross-group pairings (the classic
+    /// assignment problem). When the groups differ in size, the extras in the
+    /// larger group are left unmatched. Each match lists the `group_a` user
+    /// first.
+    pub fn find_matches_bipartite(&self, group_a: &[User], group_b: &[User]) -> Vec<Match> {
+        self.cross_matching(group_a, group_b, |_, _| true)
+    }
+
+    /// [`find_matches_bipartite`](Self::find_matches_bipartite) without
+    /// repeating any pair in `history`.
+    ///
+    /// For recurring events between two clubs: pairs that already met (in
+    /// either order) are skipped, so each user gets their best opponent they
+    /// haven't faced. Users whose every cross-group pairing is in `history`
+    /// are left unmatched.
+    pub fn match_cross_pool_avoiding(
+        &self,
+        group_a: &[User],
+        group_b: &[User],
+        history: &HashSet<(String, String)>,
+    ) -> Vec<Match> {
+        let history: HashSet<(String, String)> = history
+            .iter()
+            .map(|(x, y)| ordered_pair(x.clone(), y.clone()))
+            .collect();
+        self.cross_matching(group_a, group_b, |a, b| {
+            !history.contains(&ordered_pair(a.id.clone(), b.id.clone()))
+        })
+    }
+
+    fn cross_matching(
+        &self,
+        group_a: &[User],
+        group_b: &[User],
+        allowed: impl Fn(&User, &User) -> bool,
+    ) -> Vec<Match> {
+        let offset = group_a.len();
+        let mut scores = HashMap::new();
+        let mut edges = Vec::with_capacity(group_a.len() * group_b.len());
+        for (i, a) in group_a.iter().enumerate() {
+            for (j, b) in group_b.iter().enumerate().filter(|(_, b)| allowed(a, b)) {
+                let score = self.pair_score(a, b);
+                scores.insert((i, j), score);
+                edges.push((i, offset + j, self.apply_balance_penalty(a, b, score)));
+            }
+        }
+        let mate = max_weight_matching(offset + group_b.len(), &edges, true);
+
+        mate.iter()
+            .take(offset)
+            .enumerate()
+            .filter_map(|(i, m)| m.map(|m| (i, m - offset)))
+            .map(|(i, j)| {
+                Match::new(
+                    group_a[i].id.clone(),
+                    group_b[j].id.clone(),
+                    scores[&(i, j)],
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,30 @@
+use crate::matcher::{Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    /// [`find_matches_lenient`](Self::find_matches_lenient), named for what
+    /// it is: CPU-bound work that takes O(n²) scorer calls and must not run
+    /// on an async executor thread. Async callers should use
+    /// `find_matches_async` (behind the `tokio` feature) or their own
+    /// blocking pool.
+    pub fn find_matches_blocking(&self, users: &[User]) -> Vec<Match> {
+        self.find_matches_lenient(users)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<S: ScoringStrategy + Clone + Send + 'static> NemesisMatcher<S> {
+    /// Runs [`find_matches_blocking`](Self::find_matches_blocking) on tokio's
+    /// blocking thread pool so the event loop stays responsive.
+    ///
+    /// The matcher is cloned into the task. A panic inside the matcher is
+    /// resumed on the awaiting task.
+    pub async fn find_matches_async(&self, users: Vec<User>) -> Vec<Match> {
+        let matcher = self.clone();
+        match tokio::task::spawn_blocking(move || matcher.find_matches_blocking(&users)).await {
+            Ok(matches) => matches,
+            Err(error) => std::panic::resume_unwind(error.into_panic()),
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use crate::matcher::{id_order, unmatched_users, Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// A scored pair ordered by selection weight, then by ids on ties (smaller
+/// ids rank higher), so that heap order agrees with greedy selection.
+#[derive(Debug, Clone, Copy)]
+struct HeapPair<'a> {
+    i: usize,
+    j: usize,
+    score: f64,
+    weight: f64,
+    ids: (&'a str, &'a str),
+}
+
+impl PartialEq for HeapPair<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for HeapPair<'_> {}
+
+impl PartialOrd for HeapPair<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapPair<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.weight
+            .total_cmp(&other.weight)
+            .then_with(|| other.ids.cmp(&self.ids))
+    }
+}
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    /// Scores every pair but keeps only the `heap_size` best in memory.
+    ///
+    /// Pairs are streamed through a bounded min-heap instead of being
+    /// collected into the full `n * (n - 1) / 2` vector. "Best" is greedy
+    /// selection's order: pairs the matcher would never form (a disengaged
+    /// member, or at or below the opposition floor) are skipped, the rest
+    /// rank by their balance-penalised weight with ties broken by ids, and
+    /// the returned scores are the scorer's own.
+    pub fn top_scored_pairs(&self, users: &[User], heap_size: usize) -> Vec<(usize, usize, f64)> {
+        if heap_size == 0 {
+            return Vec::new();
+        }
+        let mut heap = BinaryHeap::with_capacity(heap_size + 1);
+        self.stream_pairs(users, |pair| push_bounded(&mut heap, pair, heap_size));
+        let mut pairs: Vec<HeapPair> = heap.into_iter().map(|Reverse(p)| p).collect();
+        pairs.sort_by(|a, b| b.cmp(a));
+        pairs.into_iter().map(|p| (p.i, p.j, p.score)).collect()
+    }
+
+    /// Scores every pair the matcher could form and hands it to `visit`,
+    /// one at a time.
+    fn stream_pairs<'a>(&self, users: &'a [User], mut visit: impl FnMut(HeapPair<'a>)) {
+        let engaged: Vec<bool> = users.iter().map(|u| self.is_engaged(u)).collect();
+        for i in (0..users.len()).filter(|&i| engaged[i]) {
+            for j in ((i + 1)..users.len()).filter(|&j| engaged[j]) {
+                let score = self.pair_score(&users[i], &users[j]);
+                let Some(weight) = self.selection_weight(&users[i], &users[j], score) else {
+                    continue;
+                };
+                let (u1, u2) = id_order(&users[i], &users[j]);
+                visit(HeapPair {
+                    i,
+                    j,
+                    score,
+                    weight,
+                    ids: (&u1.id, &u2.id),
+                });
+            }
+        }
+    }
+
+    /// Each user's best `per_user` candidates, merged without duplicates.
+    fn candidates_per_user(&self, users: &[User], per_user: usize) -> Vec<(usize, usize, f64)> {
+        let mut heaps: Vec<BinaryHeap<Reverse<HeapPair>>> = (0..users.len())
+            .map(|_| BinaryHeap::with_capacity(per_user + 1))
+            .collect();
+        self.stream_pairs(users, |pair| {
+            push_bounded(&mut heaps[pair.i], pair, per_user);
+            push_bounded(&mut heaps[pair.j], pair, per_user);
+        });
+        let mut pairs: Vec<(usize, usize, f64)> = heaps
+            .into_iter()
+            .flatten()
+            .map(|Reverse(p)| (p.i, p.j, p.score))
+            .collect();
+        pairs.sort_unstable_by_key(|&(i, j, _)| (i, j));
+        pairs.dedup_by_key(|&mut (i, j, _)| (i, j));
+        pairs
+    }
+
+    /// A leaderboard of the `n` most opposed pairs in the pool, best first.
+    ///
+    /// Unlike a matching, pairs may share users. Built on
+    /// [`top_scored_pairs`](Self::top_scored_pairs), so only `n` pairs are held
+    /// in memory; each match lists the smaller id first.
+    pub fn top_pairs(&self, users: &[User], n: usize) -> Vec<Match> {
+        self.top_scored_pairs(users, n)
+            .into_iter()
+            .map(|(i, j, score)| {
+                let (u1, u2) = id_order(&users[i], &users[j]);
+                Match::new(u1.id.clone(), u2.id.clone(), score)
+            })
+            .collect()
+    }
+
+    /// Greedy matching with memory bounded by about `heap_size` pairs.
+    ///
+    /// Pairs are streamed once, each user keeping only their best
+    /// `2 * heap_size / n` candidates (at least two, so one popular partner
+    /// can't be everyone's only option), and greedy selection runs over
+    /// those. Users whose every candidate was taken are then matched among
+    /// themselves in further passes, which rescore only pairs between
+    /// still-unmatched users. When `heap_size` covers all pairs every
+    /// candidate is kept and the result is identical to
+    /// [`find_matches`](Self::find_matches); otherwise it is an approximation
+    /// that can miss pairs a full pass would take.
+    pub fn find_matches_bounded(&self, users: &[User], heap_size: usize) -> Vec<Match> {
+        let mut matches = Vec::with_capacity(users.len() / 2);
+        let mut remaining: Vec<User> = users.to_vec();
+        while remaining.len() >= 2 {
+            let n = remaining.len();
+            let per_user = (2 * heap_size).div_ceil(n).max(2).min(n - 1);
+            let pairs = self.candidates_per_user(&remaining, per_user);
+            let found = self.greedy_select(&remaining, pairs);
+            if found.is_empty() {
+                break;
+            }
+            matches.extend(found);
+            remaining = unmatched_users(&remaining, &matches)
+                .into_iter()
+                .cloned()
+                .collect();
+        }
+        matches
+    }
+}
+
+/// Pushes `pair` onto a min-heap holding at most `cap` pairs, evicting the
+/// worst if `pair` beats it.
+fn push_bounded<'a>(heap: &mut BinaryHeap<Reverse<HeapPair<'a>>>, pair: HeapPair<'a>, cap: usize) {
+    if heap.len() < cap {
+        heap.push(Reverse(pair));
+    } else if heap.peek().is_some_and(|Reverse(worst)| pair > *worst) {
+        heap.pop();
+        heap.push(Reverse(pair));
+    }
+}
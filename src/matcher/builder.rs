@@ -0,0 +1,133 @@
+use crate::matcher::{Constraints, Match, NemesisMatcher};
+use crate::scoring::{ScoringStrategy, SimpleDifferenceScorer};
+use crate::user::User;
+
+/// What a [`ConfiguredMatcher`] optimizes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Objective {
+    /// Highest-scoring pairs first, as [`NemesisMatcher::find_matches`].
+    #[default]
+    Greedy,
+    /// The largest total score, as [`NemesisMatcher::find_matches_optimal`].
+    MaxTotal,
+}
+
+/// Collects the options for a matching run in one chain.
+///
+/// Starts from [`SimpleDifferenceScorer`], no constraints and the greedy
+/// objective; [`build`](Self::build) fixes the options into a
+/// [`ConfiguredMatcher`].
+#[derive(Debug, Clone)]
+pub struct MatchBuilder<S: ScoringStrategy> {
+    scorer: S,
+    constraints: Constraints,
+    objective: Objective,
+    opposition_floor: Option<f64>,
+}
+
+impl MatchBuilder<SimpleDifferenceScorer> {
+    pub fn new() -> Self {
+        MatchBuilder {
+            scorer: SimpleDifferenceScorer,
+            constraints: Constraints::new(),
+            objective: Objective::default(),
+            opposition_floor: None,
+        }
+    }
+}
+
+impl Default for MatchBuilder<SimpleDifferenceScorer> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: ScoringStrategy> MatchBuilder<S> {
+    /// Swaps in a different scorer, keeping every other option.
+    pub fn scorer<T: ScoringStrategy>(self, scorer: T) -> MatchBuilder<T> {
+        MatchBuilder {
+            scorer,
+            constraints: self.constraints,
+            objective: self.objective,
+            opposition_floor: self.opposition_floor,
+        }
+    }
+
+    /// Forbids pairs scoring below `min_score`.
+    pub fn threshold(mut self, min_score: f64) -> Self {
+        self.constraints = self.constraints.min_score(min_score);
+        self
+    }
+
+    /// Forbids pairing `a` with `b`; call once per pair.
+    pub fn exclude(mut self, a: impl Into<String>, b: impl Into<String>) -> Self {
+        self.constraints = self.constraints.exclude(a, b);
+        self
+    }
+
+    /// Replaces all constraints, for rules the shorthands above don't cover.
+    pub fn constraints(mut self, constraints: Constraints) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    pub fn objective(mut self, objective: Objective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    /// Leaves pairs scoring `epsilon` or less unmatched; see
+    /// [`NemesisMatcher::with_opposition_floor`].
+    pub fn opposition_floor(mut self, epsilon: f64) -> Self {
+        self.opposition_floor = Some(epsilon);
+        self
+    }
+
+    pub fn build(self) -> ConfiguredMatcher<S> {
+        let mut matcher = NemesisMatcher::new(self.scorer);
+        if let Some(epsilon) = self.opposition_floor {
+            matcher = matcher.with_opposition_floor(epsilon);
+        }
+        ConfiguredMatcher {
+            matcher,
+            constraints: self.constraints,
+            objective: self.objective,
+        }
+    }
+}
+
+/// A matcher with its options fixed by [`MatchBuilder`].
+#[derive(Debug, Clone)]
+pub struct ConfiguredMatcher<S: ScoringStrategy> {
+    matcher: NemesisMatcher<S>,
+    constraints: Constraints,
+    objective: Objective,
+}
+
+impl<S: ScoringStrategy> ConfiguredMatcher<S> {
+    pub fn matcher(&self) -> &NemesisMatcher<S> {
+        &self.matcher
+    }
+
+    pub fn constraints(&self) -> &Constraints {
+        &self.constraints
+    }
+
+    pub fn objective(&self) -> Objective {
+        self.objective
+    }
+
+    /// Matches `users` under the configured constraints and objective.
+    pub fn find_matches(&self, users: &[User]) -> Vec<Match> {
+        match self.objective {
+            Objective::Greedy => self
+                .matcher
+                .find_matches_constrained(users, &self.constraints),
+            Objective::MaxTotal => {
+                self.matcher
+                    .find_matches_optimal_constrained(users, &self.constraints)
+                    .0
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::error::NemesisError;
+use crate::matcher::{id_order, Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    /// [`calculate_all_pairs`](Self::calculate_all_pairs), failing on the
+    /// first pair whose score is NaN or infinite.
+    pub fn try_calculate_all_pairs(
+        &self,
+        users: &[User],
+    ) -> Result<Vec<(usize, usize, f64)>, NemesisError> {
+        let mut pairs = Vec::with_capacity(users.len() * users.len().saturating_sub(1) / 2);
+        for i in 0..users.len() {
+            for j in (i + 1)..users.len() {
+                let (first, second) = id_order(&users[i], &users[j]);
+                pairs.push((i, j, self.scorer.try_calculate_score(first, second)?));
+            }
+        }
+        Ok(pairs)
+    }
+
+    /// [`greedy_select`](Self::greedy_select), but rejects pairs whose
+    /// indices don't fit `users` with [`NemesisError::PairIndexOutOfBounds`]
+    /// instead of panicking. Use it when `pairs` didn't come from scoring
+    /// this exact `users` slice.
+    pub fn try_greedy_select(
+        &self,
+        users: &[User],
+        pairs: Vec<(usize, usize, f64)>,
+    ) -> Result<Vec<Match>, NemesisError> {
+        if let Some(index) = pairs
+            .iter()
+            .flat_map(|&(i, j, _)| [i, j])
+            .find(|&index| index >= users.len())
+        {
+            return Err(NemesisError::PairIndexOutOfBounds {
+                index,
+                len: users.len(),
+            });
+        }
+        Ok(self.greedy_select(users, pairs))
+    }
+
+    /// [`find_matches`](Self::find_matches), but also surfaces a
+    /// misbehaving scorer as [`NemesisError::NonFiniteScore`] instead of
+    /// letting it silently corrupt the greedy order.
+    pub fn try_find_matches(&self, users: &[User]) -> Result<Vec<Match>, NemesisError> {
+        self.check_pool_size(users)?;
+        let pairs = self.try_calculate_all_pairs(users)?;
+        self.try_greedy_select(users, pairs)
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::matcher::blossom::max_weight_matching;
+use crate::matcher::{matches_from_mates, Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// A rule over one of the users' [`tags`](User::tags).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagConstraint {
+    /// Users sharing a value for this tag may not be paired. Users missing
+    /// the tag are unrestricted.
+    MustDiffer(String),
+    /// Users may only be paired if both have this tag with the same value.
+    MustMatch(String),
+}
+
+impl TagConstraint {
+    pub fn allows(&self, user1: &User, user2: &User) -> bool {
+        match self {
+            TagConstraint::MustDiffer(key) => match (user1.tag(key), user2.tag(key)) {
+                (Some(a), Some(b)) => a != b,
+                _ => true,
+            },
+            TagConstraint::MustMatch(key) => match (user1.tag(key), user2.tag(key)) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Restrictions on which pairs a matcher may form.
+///
+/// * exclusions forbid specific id pairs (in either order);
+/// * group labels forbid pairing two users that share a label, which is how
+///   bipartite events are expressed (users without a label are unrestricted);
+/// * tag rules require pairs to differ or agree on a user tag;
+/// * a minimum score forbids pairs that aren't opposed enough to be worth it;
+/// * a clash limit forbids pairs that disagree strongly on too many questions.
+///
+/// A forbidden pair is skipped no matter how well it scores.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Constraints {
+    excluded: HashSet<(String, String)>,
+    groups: HashMap<String, String>,
+    tag_rules: Vec<TagConstraint>,
+    min_score: Option<f64>,
+    clash_limit: Option<(i32, usize)>,
+}
+
+impl Constraints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forbids pairing `a` with `b`.
+    pub fn exclude(mut self, a: impl Into<String>, b: impl Into<String>) -> Self {
+        self.excluded.insert(ordered_pair(a.into(), b.into()));
+        self
+    }
+
+    /// Puts `id` in group `label`; users sharing a label are never paired.
+    pub fn group(mut self, id: impl Into<String>, label: impl Into<String>) -> Self {
+        self.groups.insert(id.into(), label.into());
+        self
+    }
+
+    /// Adds a tag rule every pair must satisfy.
+    pub fn tag_rule(mut self, rule: TagConstraint) -> Self {
+        self.tag_rules.push(rule);
+        self
+    }
+
+    /// Forbids pairs scoring below `threshold`.
+    pub fn min_score(mut self, threshold: f64) -> Self {
+        self.min_score = Some(threshold);
+        self
+    }
+
+    /// Forbids pairs whose answers differ by `threshold` or more on more
+    /// than `max_clashes` questions, to keep debates civil. A `threshold` of
+    /// 0 or less counts every shared question as a clash.
+    pub fn max_strong_clashes(mut self, threshold: i32, max_clashes: usize) -> Self {
+        self.clash_limit = Some((threshold, max_clashes));
+        self
+    }
+
+    pub fn is_excluded(&self, a: &str, b: &str) -> bool {
+        self.excluded
+            .contains(&ordered_pair(a.to_string(), b.to_string()))
+    }
+
+    /// Whether `user1` and `user2` may be paired given their `score`.
+    pub fn allows(&self, user1: &User, user2: &User, score: f64) -> bool {
+        if self.is_excluded(&user1.id, &user2.id) {
+            return false;
+        }
+        if let (Some(g1), Some(g2)) = (self.groups.get(&user1.id), self.groups.get(&user2.id)) {
+            if g1 == g2 {
+                return false;
+            }
+        }
+        if !self.tag_rules.iter().all(|rule| rule.allows(user1, user2)) {
+            return false;
+        }
+        if let Some((threshold, max_clashes)) = self.clash_limit {
+            let clashes = user1
+                .responses
+                .iter()
+                .zip(&user2.responses)
+                .filter(|&(&a, &b)| (a as i64 - b as i64).abs() >= threshold as i64)
+                .count();
+            if clashes > max_clashes {
+                return false;
+            }
+        }
+        self.min_score.is_none_or(|t| score >= t)
+    }
+}
+
+/// The two ids with the smaller first, for order-insensitive pair keys.
+pub(crate) fn ordered_pair(a: String, b: String) -> (String, String) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Whether a complete matching exists under a set of constraints.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Feasibility {
+    /// Everyone can be paired (all but one for odd pools).
+    Feasible,
+    /// Even the largest possible matching leaves these users out, beyond the
+    /// single leftover an odd pool would have anyway.
+    Infeasible { unplaceable: Vec<String> },
+}
+
+impl Feasibility {
+    pub fn is_feasible(&self) -> bool {
+        matches!(self, Feasibility::Feasible)
+    }
+}
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    /// Scores every pair, dropping those the constraints forbid.
+    pub fn calculate_allowed_pairs(
+        &self,
+        users: &[User],
+        constraints: &Constraints,
+    ) -> Vec<(usize, usize, f64)> {
+        let pairs = self.calculate_all_pairs(users);
+        let scored = pairs.len();
+        let allowed: Vec<(usize, usize, f64)> = pairs
+            .into_iter()
+            .filter(|&(i, j, score)| constraints.allows(&users[i], &users[j], score))
+            .collect();
+        debug_log!(
+            "constraints skipped {} of {} scored pairs",
+            scored - allowed.len(),
+            scored
+        );
+        allowed
+    }
+
+    /// Greedy matching that never forms a forbidden pair.
+    ///
+    /// Over-constrained pools come back partially matched; call
+    /// [`feasibility`](Self::feasibility) first to find out who can't be placed.
+    pub fn find_matches_constrained(
+        &self,
+        users: &[User],
+        constraints: &Constraints,
+    ) -> Vec<Match> {
+        let pairs = self.calculate_allowed_pairs(users, constraints);
+        self.greedy_select(users, pairs)
+    }
+
+    /// Checks whether the constraints still allow everyone to be matched.
+    ///
+    /// Runs a maximum-cardinality matching over the allowed pairs. If it falls
+    /// short, the users that matching leaves out are reported; users with no
+    /// allowed partner at all always appear in that list.
+    pub fn feasibility(&self, users: &[User], constraints: &Constraints) -> Feasibility {
+        let allowed = self.calculate_allowed_pairs(users, constraints);
+        let edges: Vec<(usize, usize, f64)> = self
+            .selection_weights(users, &allowed)
+            .into_iter()
+            .map(|(i, j, _)| (i, j, 1.0))
+            .collect();
+        let mate = max_weight_matching(users.len(), &edges, true);
+        feasibility_of(users, &mate)
+    }
+
+    /// The highest-total matching that never forms a forbidden pair.
+    ///
+    /// The blossom counterpart of
+    /// [`find_matches_constrained`](Self::find_matches_constrained): it first
+    /// matches as many users as the constraints allow, then maximises total
+    /// score among those matchings. When the constraints rule out a complete
+    /// matching, the matches are still the best feasible ones and the
+    /// [`Feasibility`] names who was left out, exactly as
+    /// [`feasibility`](Self::feasibility) would.
+    pub fn find_matches_optimal_constrained(
+        &self,
+        users: &[User],
+        constraints: &Constraints,
+    ) -> (Vec<Match>, Feasibility) {
+        let pairs = self.calculate_allowed_pairs(users, constraints);
+        let weights = self.selection_weights(users, &pairs);
+        let mate = max_weight_matching(users.len(), &weights, true);
+        let feasibility = feasibility_of(users, &mate);
+        (matches_from_mates(users, pairs, &mate), feasibility)
+    }
+}
+
+/// Reads feasibility off a maximum-cardinality mate vector.
+fn feasibility_of(users: &[User], mate: &[Option<usize>]) -> Feasibility {
+    let matched = mate.iter().filter(|m| m.is_some()).count();
+    if matched == users.len() - users.len() % 2 {
+        return Feasibility::Feasible;
+    }
+    Feasibility::Infeasible {
+        unplaceable: users
+            .iter()
+            .zip(mate)
+            .filter(|(_, m)| m.is_none())
+            .map(|(u, _)| u.id.clone())
+            .collect(),
+    }
+}
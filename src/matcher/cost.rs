@@ -0,0 +1,57 @@
+use crate::matcher::Objective;
+
+/// Rough wall-clock bracket for a [`CostEstimate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TimeClass {
+    /// Well under a millisecond.
+    Instant,
+    /// Up to about a second; fine to run inside a request.
+    Interactive,
+    /// Seconds to a minute or two; consider a background job.
+    Slow,
+    /// Longer than that; queue it.
+    Batch,
+}
+
+/// A back-of-the-envelope cost for matching a pool, from [`estimate_cost`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostEstimate {
+    /// Pairs that will be scored: `n * (n - 1) / 2`.
+    pub pairs: usize,
+    /// Approximate number of basic steps after scoring.
+    pub operations: f64,
+    pub time_class: TimeClass,
+}
+
+/// Estimates how expensive matching `num_users` users will be, e.g. to
+/// decide between answering synchronously and queueing a job.
+///
+/// Greedy costs a sort of the scored pairs, `p · log₂ p` for `p` pairs;
+/// optimal costs the blossom algorithm's `n³`. This is a heuristic from the
+/// algorithms' complexity, not a benchmark: it ignores scorer cost and
+/// hardware, and the time classes assume roughly 10⁸ simple steps per
+/// second.
+pub fn estimate_cost(num_users: usize, algorithm: Objective) -> CostEstimate {
+    let pairs = num_users * num_users.saturating_sub(1) / 2;
+    let operations = match algorithm {
+        Objective::Greedy => {
+            let p = pairs as f64;
+            p * p.log2().max(1.0)
+        }
+        Objective::MaxTotal => (num_users as f64).powi(3),
+    };
+    let time_class = if operations < 1e5 {
+        TimeClass::Instant
+    } else if operations < 1e8 {
+        TimeClass::Interactive
+    } else if operations < 1e10 {
+        TimeClass::Slow
+    } else {
+        TimeClass::Batch
+    };
+    CostEstimate {
+        pairs,
+        operations,
+        time_class,
+    }
+}
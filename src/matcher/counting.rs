@@ -0,0 +1,62 @@
+use std::collections::HashSet;
+
+use crate::error::NemesisError;
+
+/// Largest pool [`count_perfect_matchings`] accepts.
+pub const MAX_COUNTED_USERS: usize = 20;
+
+/// How many distinct perfect matchings of `n` users avoid every excluded pair.
+///
+/// Users are indices `0..n`; an exclusion `(i, j)` forbids that pair in
+/// either order. Gives 0 when no perfect matching exists, including for
+/// odd `n`, and 1 for an empty pool. Counting is exponential in `n` (a
+/// memoised walk over subsets of users), so it is meant for small events
+/// and returns [`NemesisError::PoolTooLarge`] if `n` exceeds
+/// [`MAX_COUNTED_USERS`]; unconstrained, 20 users already have 654,729,075
+/// matchings.
+pub fn count_perfect_matchings(
+    n: usize,
+    exclusions: &HashSet<(usize, usize)>,
+) -> Result<u64, NemesisError> {
+    if n > MAX_COUNTED_USERS {
+        return Err(NemesisError::PoolTooLarge {
+            got: n,
+            max: MAX_COUNTED_USERS,
+        });
+    }
+    if n % 2 == 1 {
+        return Ok(0);
+    }
+    let allowed =
+        |i: usize, j: usize| !exclusions.contains(&(i, j)) && !exclusions.contains(&(j, i));
+    // counts[mask] = matchings of the users whose bits are set, once known.
+    let mut counts: Vec<Option<u64>> = vec![None; 1 << n];
+    Ok(count_from((1 << n) - 1, &allowed, &mut counts))
+}
+
+/// Matches the lowest remaining user with each allowed partner in turn.
+fn count_from(
+    remaining: usize,
+    allowed: &impl Fn(usize, usize) -> bool,
+    counts: &mut [Option<u64>],
+) -> u64 {
+    if remaining == 0 {
+        return 1;
+    }
+    if let Some(count) = counts[remaining] {
+        return count;
+    }
+    let first = remaining.trailing_zeros() as usize;
+    let rest = remaining & !(1 << first);
+    let mut total = 0;
+    let mut candidates = rest;
+    while candidates != 0 {
+        let partner = candidates.trailing_zeros() as usize;
+        candidates &= candidates - 1;
+        if allowed(first, partner) {
+            total += count_from(rest & !(1 << partner), allowed, counts);
+        }
+    }
+    counts[remaining] = Some(total);
+    total
+}
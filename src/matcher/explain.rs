@@ -0,0 +1,45 @@
+use std::collections::HashSet;
+
+use crate::matcher::{id_order, Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    /// Greedy matching plus an ordered, human-readable log of every decision.
+    ///
+    /// The log has one line per pair considered, in the order greedy visits
+    /// them, saying whether it was matched or skipped and why. It produces the
+    /// same matches as [`find_matches`](Self::find_matches); use that on the
+    /// hot path, since building the log allocates a string per pair.
+    pub fn find_matches_explained(&self, users: &[User]) -> (Vec<Match>, Vec<String>) {
+        let pairs = self.rank_pairs(users, &self.calculate_all_pairs(users));
+
+        let mut matched: HashSet<&str> = HashSet::new();
+        let mut matches = Vec::with_capacity(users.len() / 2);
+        let mut log = Vec::with_capacity(pairs.len() + 1);
+        for (i, j, score) in pairs {
+            let (u1, u2) = id_order(&users[i], &users[j]);
+            let taken: Vec<&str> = [u1.id.as_str(), u2.id.as_str()]
+                .into_iter()
+                .filter(|id| matched.contains(id))
+                .collect();
+            if !taken.is_empty() {
+                log.push(format!(
+                    "skipped {} & {} (score {score:.2}): {} already matched",
+                    u1.id,
+                    u2.id,
+                    taken.join(" and ")
+                ));
+                continue;
+            }
+            matched.insert(&u1.id);
+            matched.insert(&u2.id);
+            log.push(format!("matched {} & {} (score {score:.2})", u1.id, u2.id));
+            matches.push(Match::new(u1.id.clone(), u2.id.clone(), score));
+        }
+        for user in users.iter().filter(|u| !matched.contains(u.id.as_str())) {
+            log.push(format!("{} left unmatched", user.id));
+        }
+        (matches, log)
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::NemesisError;
+use crate::matcher::{Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    /// Matches with some pairs locked in advance.
+    ///
+    /// The `fixed` pairs come first in the result, scored for the record but
+    /// kept regardless of score; everyone else is then matched greedily.
+    /// Errors if a fixed id isn't in `users` or appears in more than one
+    /// fixed pair (including being paired with itself).
+    pub fn find_matches_with_fixed(
+        &self,
+        users: &[User],
+        fixed: &[(String, String)],
+    ) -> Result<Vec<Match>, NemesisError> {
+        let by_id: HashMap<&str, &User> = users.iter().map(|u| (u.id.as_str(), u)).collect();
+        let mut locked: HashSet<&str> = HashSet::new();
+        let mut matches = Vec::with_capacity(users.len() / 2);
+        for (a, b) in fixed {
+            let mut lookup = |id: &str| {
+                let user = *by_id
+                    .get(id)
+                    .ok_or_else(|| NemesisError::UnknownUserId(id.to_string()))?;
+                if !locked.insert(&user.id) {
+                    return Err(NemesisError::DuplicateUserId(id.to_string()));
+                }
+                Ok(user)
+            };
+            let (u1, u2) = (lookup(a)?, lookup(b)?);
+            matches.push(Match::new(
+                u1.id.clone(),
+                u2.id.clone(),
+                self.pair_score(u1, u2),
+            ));
+        }
+
+        let rest: Vec<User> = users
+            .iter()
+            .filter(|u| !locked.contains(u.id.as_str()))
+            .cloned()
+            .collect();
+        matches.extend(self.find_matches_lenient(&rest));
+        Ok(matches)
+    }
+}
@@ -0,0 +1,68 @@
+use crate::matcher::{Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// Decides who sits out when a pool has an odd number of users.
+pub trait LeftoverPolicy {
+    /// Index into `users` of the user to leave unmatched. Only called with
+    /// an odd, non-empty pool.
+    fn choose_leftover(&self, users: &[User]) -> usize;
+}
+
+/// Leaves out the user with the lowest [`engagement`](User::engagement),
+/// the earliest in the slice on ties.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DropLowestEngagement;
+
+impl LeftoverPolicy for DropLowestEngagement {
+    fn choose_leftover(&self, users: &[User]) -> usize {
+        let mut lowest = 0;
+        for (index, user) in users.iter().enumerate() {
+            if user.engagement() < users[lowest].engagement() {
+                lowest = index;
+            }
+        }
+        lowest
+    }
+}
+
+/// Leaves out the last user in the slice, e.g. the newest signup when the
+/// pool is in arrival order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DropLast;
+
+impl LeftoverPolicy for DropLast {
+    fn choose_leftover(&self, users: &[User]) -> usize {
+        users.len().saturating_sub(1)
+    }
+}
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    /// [`find_matches`](Self::find_matches) with `policy` choosing who sits
+    /// out of an odd pool.
+    ///
+    /// The chosen user is removed before greedy matching, so everyone else is
+    /// paired (subject to the usual engagement and floor filters). Even pools
+    /// never consult the policy, and an out-of-range choice falls back to
+    /// plain `find_matches`.
+    pub fn find_matches_with_leftover<P: LeftoverPolicy + ?Sized>(
+        &self,
+        users: &[User],
+        policy: &P,
+    ) -> Vec<Match> {
+        if users.len().is_multiple_of(2) {
+            return self.find_matches_lenient(users);
+        }
+        let leftover = policy.choose_leftover(users);
+        if leftover >= users.len() {
+            return self.find_matches_lenient(users);
+        }
+        let rest: Vec<User> = users
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| index != leftover)
+            .map(|(_, u)| u.clone())
+            .collect();
+        self.find_matches_lenient(&rest)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::matcher::blossom::max_weight_matching;
+use crate::matcher::{Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// Matches everyone so that the *weakest* pairing is as strong as possible.
+///
+/// This is a different objective from [`NemesisMatcher::find_matches_optimal`]:
+/// maximising the total can happily accept one lame pairing if it buys a
+/// spectacular one elsewhere, whereas maximin guarantees nobody gets a dull
+/// nemesis. It binary-searches the largest score threshold that still admits
+/// a complete matching, then maximises the total among pairs at or above it.
+#[derive(Debug, Clone)]
+pub struct MaximinMatcher<S: ScoringStrategy> {
+    matcher: NemesisMatcher<S>,
+}
+
+impl<S: ScoringStrategy> MaximinMatcher<S> {
+    pub fn new(scorer: S) -> Self {
+        MaximinMatcher {
+            matcher: NemesisMatcher::new(scorer),
+        }
+    }
+
+    pub fn find_matches(&self, users: &[User]) -> Vec<Match> {
+        let pairs = self.matcher.calculate_all_pairs(users);
+        let target = users.len() / 2;
+        let mut thresholds: Vec<f64> = pairs.iter().map(|p| p.2).collect();
+        thresholds.sort_by(f64::total_cmp);
+        thresholds.dedup();
+
+        let edges_from = |threshold: f64| -> Vec<(usize, usize, f64)> {
+            pairs.iter().copied().filter(|p| p.2 >= threshold).collect()
+        };
+        let complete = |threshold: f64| {
+            let unit: Vec<_> = edges_from(threshold)
+                .into_iter()
+                .map(|(i, j, _)| (i, j, 1.0))
+                .collect();
+            let mate = max_weight_matching(users.len(), &unit, true);
+            mate.iter().filter(|m| m.is_some()).count() / 2 == target
+        };
+
+        // The lowest threshold always admits every pair, so search above it.
+        let (mut low, mut high) = (0, thresholds.len());
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            if complete(thresholds[mid]) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        let edges = thresholds
+            .get(low)
+            .map_or_else(Vec::new, |&t| edges_from(t));
+        let mate = max_weight_matching(users.len(), &edges, true);
+        edges
+            .iter()
+            .filter(|&&(i, j, _)| mate[i] == Some(j))
+            .map(|&(i, j, score)| Match::new(users[i].id.clone(), users[j].id.clone(), score))
+            .collect()
+    }
+}
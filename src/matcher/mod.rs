@@ -0,0 +1,533 @@
+//! Pairing users with their most opposed counterpart.
+
+mod approx;
+mod bipartite;
+mod blocking;
+pub mod blossom;
+mod bounded;
+mod builder;
+mod checked;
+mod constraints;
+mod cost;
+mod counting;
+mod explain;
+mod fixed;
+mod leftover;
+mod maximin;
+mod opponents;
+mod partition;
+mod percentile;
+mod requests;
+mod sort;
+mod stats;
+mod swing;
+mod targeted;
+mod teams;
+mod timed;
+mod tournament;
+mod validate;
+mod weighted_random;
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::NemesisError;
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+use self::blossom::max_weight_matching;
+
+pub use self::approx::ApproxMatcher;
+pub use self::builder::{ConfiguredMatcher, MatchBuilder, Objective};
+pub(crate) use self::constraints::ordered_pair;
+pub use self::constraints::{Constraints, Feasibility, TagConstraint};
+pub use self::cost::{estimate_cost, CostEstimate, TimeClass};
+pub use self::counting::{count_perfect_matchings, MAX_COUNTED_USERS};
+pub use self::leftover::{DropLast, DropLowestEngagement, LeftoverPolicy};
+pub use self::maximin::MaximinMatcher;
+pub use self::partition::partition_pool;
+pub use self::percentile::label_matches;
+pub use self::sort::{sort_matches, SortKey};
+pub use self::swing::{minimal_question_subset, swing_questions};
+pub use self::teams::match_blocks;
+pub use self::timed::MatchOutcome;
+pub use self::tournament::TournamentScheduler;
+pub use self::validate::validate_matching;
+pub use self::weighted_random::WeightedRandomMatcher;
+
+/// One pairing produced by a matcher, with the opposition score that justified it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Match {
+    pub user1_id: String,
+    pub user2_id: String,
+    pub score: f64,
+    /// Where `score` falls in the pool's distribution of pair scores, from
+    /// 0 to 100; set by [`label_matches`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percentile: Option<f64>,
+}
+
+impl Match {
+    pub fn new(user1_id: impl Into<String>, user2_id: impl Into<String>, score: f64) -> Self {
+        Match {
+            user1_id: user1_id.into(),
+            user2_id: user2_id.into(),
+            score,
+            percentile: None,
+        }
+    }
+
+    /// A copy with `score` rounded to `decimals` places, halves away from
+    /// zero (so 2.5 becomes 3 and 0.125 becomes 0.13 at 2 decimals, up to
+    /// float representation).
+    ///
+    /// Prefer this over formatting with `{:.2}`, which rounds halves to even
+    /// and yields a string rather than a score.
+    pub fn rounded(&self, decimals: u32) -> Match {
+        let factor = 10f64.powi(decimals as i32);
+        Match {
+            score: (self.score * factor).round() / factor,
+            ..self.clone()
+        }
+    }
+
+    /// Returns `true` if `id` is either side of this match.
+    pub fn involves(&self, id: &str) -> bool {
+        self.user1_id == id || self.user2_id == id
+    }
+}
+
+/// Pairs users so that each is matched with someone who disagrees with them.
+///
+/// The default [`find_matches`](Self::find_matches) is greedy: score every
+/// pair, then repeatedly take the highest-scoring pair whose members are both
+/// still free. With an odd number of users one is left unmatched.
+#[derive(Debug, Clone)]
+pub struct NemesisMatcher<S: ScoringStrategy> {
+    scorer: S,
+    drop_counts: HashMap<String, usize>,
+    last_leftovers: Vec<String>,
+    min_pool_size: usize,
+    balance_penalty: f64,
+    min_engagement: Option<f64>,
+    opposition_floor: Option<f64>,
+}
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    pub fn new(scorer: S) -> Self {
+        NemesisMatcher {
+            scorer,
+            drop_counts: HashMap::new(),
+            last_leftovers: Vec::new(),
+            min_pool_size: 2,
+            balance_penalty: 0.0,
+            min_engagement: None,
+            opposition_floor: None,
+        }
+    }
+
+    pub fn scorer(&self) -> &S {
+        &self.scorer
+    }
+
+    /// Discounts pairs where one user drives most of the opposition.
+    ///
+    /// Each user's conviction is their [`engagement`](User::engagement).
+    /// With convictions `c1` and `c2`, matching ranks a pair by
+    /// `score · (1 - factor · |c1 - c2| / max(c1, c2))`, so a fully one-sided
+    /// pair loses `factor` of its score and an evenly matched pair keeps it
+    /// all. The penalty only steers selection: [`Match::score`] stays the
+    /// scorer's value. `factor` is clamped to `0.0..=1.0`; 0 (the default)
+    /// disables it.
+    pub fn with_balance_penalty(mut self, factor: f64) -> Self {
+        self.balance_penalty = factor.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Keeps users whose [`engagement`](User::engagement) is below
+    /// `threshold` out of every matching selected from
+    /// [`calculate_all_pairs`](Self::calculate_all_pairs), so near-neutral
+    /// users don't soak up partners. List them with
+    /// [`disengaged_users`](Self::disengaged_users).
+    pub fn with_min_engagement(mut self, threshold: f64) -> Self {
+        self.min_engagement = Some(threshold);
+        self
+    }
+
+    /// Smallest pool [`find_matches`](Self::find_matches) and
+    /// [`try_find_matches`](Self::try_find_matches) accept; defaults to 2,
+    /// the fewest users that can form a pair. Raise it when smaller events
+    /// aren't worth running.
+    pub fn with_min_pool_size(mut self, min: usize) -> Self {
+        self.min_pool_size = min;
+        self
+    }
+
+    /// Treats pairs scoring `epsilon` or less as non-matches.
+    ///
+    /// Identical (or near-identical) users have no opposition to offer, so
+    /// rather than pairing them as a last resort they are left over. Use 0.0
+    /// to drop only exact zero-opposition pairs. The floor applies to the
+    /// scorer's score, before any balance penalty, in every matching
+    /// selected from [`calculate_all_pairs`](Self::calculate_all_pairs).
+    pub fn with_opposition_floor(mut self, epsilon: f64) -> Self {
+        self.opposition_floor = Some(epsilon);
+        self
+    }
+
+    fn above_floor(&self, score: f64) -> bool {
+        self.opposition_floor.is_none_or(|floor| score > floor)
+    }
+
+    /// Users the [`with_min_engagement`](Self::with_min_engagement)
+    /// threshold excludes from pairing.
+    pub fn disengaged_users<'a>(&self, users: &'a [User]) -> Vec<&'a User> {
+        users.iter().filter(|u| !self.is_engaged(u)).collect()
+    }
+
+    fn is_engaged(&self, user: &User) -> bool {
+        self.min_engagement
+            .is_none_or(|threshold| user.engagement() >= threshold)
+    }
+
+    /// The scorer's score for the pair, taken with the smaller id first so
+    /// that asymmetric scorers don't make results depend on input order.
+    fn pair_score(&self, user1: &User, user2: &User) -> f64 {
+        let (first, second) = id_order(user1, user2);
+        self.scorer.calculate_score(first, second)
+    }
+
+    fn apply_balance_penalty(&self, user1: &User, user2: &User, score: f64) -> f64 {
+        if self.balance_penalty == 0.0 {
+            return score;
+        }
+        let (c1, c2) = (user1.engagement(), user2.engagement());
+        let stronger = c1.max(c2);
+        if stronger == 0.0 {
+            return score;
+        }
+        score * (1.0 - self.balance_penalty * (c1 - c2).abs() / stronger)
+    }
+
+    /// Greedily matches users, highest-opposition pairs first.
+    ///
+    /// A pool smaller than the [minimum pool size](Self::with_min_pool_size)
+    /// is rejected with [`NemesisError::PoolTooSmall`], so callers can tell a
+    /// pool that was too small apart from one with no acceptable pairs. Use
+    /// [`find_matches_lenient`](Self::find_matches_lenient) to get an empty
+    /// result instead.
+    pub fn find_matches(&self, users: &[User]) -> Result<Vec<Match>, NemesisError> {
+        self.check_pool_size(users)?;
+        Ok(self.find_matches_lenient(users))
+    }
+
+    /// [`find_matches`](Self::find_matches) without the pool size check: any
+    /// pool is matched, and one too small to pair gives an empty result.
+    pub fn find_matches_lenient(&self, users: &[User]) -> Vec<Match> {
+        let pairs = self.calculate_all_pairs(users);
+        self.greedy_select(users, pairs)
+    }
+
+    fn check_pool_size(&self, users: &[User]) -> Result<(), NemesisError> {
+        if users.len() < self.min_pool_size {
+            return Err(NemesisError::PoolTooSmall {
+                got: users.len(),
+                min: self.min_pool_size,
+            });
+        }
+        Ok(())
+    }
+
+    /// Scores every unordered pair, returning `(i, j, score)` with `i < j`.
+    ///
+    /// Each pair is scored with its smaller id first, whatever the order in
+    /// `users`. Scores are the scorer's own, for every pair: the
+    /// [`min_engagement`](Self::with_min_engagement) threshold,
+    /// [`opposition_floor`](Self::with_opposition_floor) and
+    /// [`balance_penalty`](Self::with_balance_penalty) only take effect when
+    /// pairs are selected, so statistics built on this see the whole pool.
+    pub fn calculate_all_pairs(&self, users: &[User]) -> Vec<(usize, usize, f64)> {
+        let mut pairs = Vec::with_capacity(users.len() * users.len().saturating_sub(1) / 2);
+        for i in 0..users.len() {
+            for j in (i + 1)..users.len() {
+                pairs.push((i, j, self.pair_score(&users[i], &users[j])));
+            }
+        }
+        debug_log!("scored {} pairs among {} users", pairs.len(), users.len());
+        pairs
+    }
+
+    /// The pairs this matcher may form, each with the weight it is selected
+    /// by: its score after any balance penalty. Pairs with a disengaged
+    /// member or at or below the opposition floor are dropped.
+    fn selectable(
+        &self,
+        users: &[User],
+        pairs: &[(usize, usize, f64)],
+    ) -> Vec<((usize, usize, f64), f64)> {
+        let engaged: Vec<bool> = users.iter().map(|u| self.is_engaged(u)).collect();
+        pairs
+            .iter()
+            .filter(|&&(i, j, _)| engaged[i] && engaged[j])
+            .filter_map(|&(i, j, score)| {
+                let weight = self.selection_weight(&users[i], &users[j], score)?;
+                Some(((i, j, score), weight))
+            })
+            .collect()
+    }
+
+    /// The weight a scored pair is selected by, or `None` if it is at or
+    /// below the opposition floor. Engagement is checked per user, by the
+    /// caller.
+    fn selection_weight(&self, user1: &User, user2: &User, score: f64) -> Option<f64> {
+        self.above_floor(score)
+            .then(|| self.apply_balance_penalty(user1, user2, score))
+    }
+
+    /// [`selectable`](Self::selectable) pairs as edges weighted for blossom
+    /// matching.
+    fn selection_weights(
+        &self,
+        users: &[User],
+        pairs: &[(usize, usize, f64)],
+    ) -> Vec<(usize, usize, f64)> {
+        self.selectable(users, pairs)
+            .into_iter()
+            .map(|((i, j, _), weight)| (i, j, weight))
+            .collect()
+    }
+
+    /// [`selectable`](Self::selectable) pairs in greedy visiting order:
+    /// highest weight first, ties by the pair's ids. Scores are left as given.
+    fn rank_pairs(
+        &self,
+        users: &[User],
+        pairs: &[(usize, usize, f64)],
+    ) -> Vec<(usize, usize, f64)> {
+        let ids = |&(i, j, _): &(usize, usize, f64)| {
+            let (u1, u2) = id_order(&users[i], &users[j]);
+            (u1.id.as_str(), u2.id.as_str())
+        };
+        let mut ranked = self.selectable(users, pairs);
+        ranked.sort_by(|(a, wa), (b, wb)| {
+            wb.partial_cmp(wa)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| ids(a).cmp(&ids(b)))
+        });
+        ranked.into_iter().map(|(pair, _)| pair).collect()
+    }
+
+    /// Walks `pairs` from highest to lowest score, keeping each pair whose
+    /// members are both unmatched.
+    ///
+    /// The matcher's engagement threshold, opposition floor and balance
+    /// penalty are applied here, to decide which pairs are eligible and in
+    /// what order; each match keeps the score it had in `pairs`. Ties are
+    /// broken by the pair's ids and each match lists the smaller id first, so
+    /// the result depends only on ids and scores, never on where users sit in
+    /// `users`.
+    pub fn greedy_select(&self, users: &[User], pairs: Vec<(usize, usize, f64)>) -> Vec<Match> {
+        self.greedy_indices(users, &pairs)
+            .into_iter()
+            .map(|(i, j, score)| Match::new(users[i].id.clone(), users[j].id.clone(), score))
+            .collect()
+    }
+
+    /// The greedy selection behind [`greedy_select`](Self::greedy_select), as
+    /// index tuples with the smaller id's index first.
+    fn greedy_indices(
+        &self,
+        users: &[User],
+        pairs: &[(usize, usize, f64)],
+    ) -> Vec<(usize, usize, f64)> {
+        let ranked = self.rank_pairs(users, pairs);
+        if let Some(&(_, _, top)) = ranked.first() {
+            debug_log!("top pair score {top}");
+        }
+
+        let mut matched: HashSet<&str> = HashSet::new();
+        let mut selected = Vec::with_capacity(users.len() / 2);
+        for (i, j, score) in ranked {
+            let (i, j) = if users[i].id <= users[j].id {
+                (i, j)
+            } else {
+                (j, i)
+            };
+            let (id1, id2) = (users[i].id.as_str(), users[j].id.as_str());
+            if matched.contains(id1) || matched.contains(id2) {
+                continue;
+            }
+            matched.insert(id1);
+            matched.insert(id2);
+            selected.push((i, j, score));
+        }
+        debug_log!(
+            "{} of {} users left unmatched",
+            users.len() - 2 * selected.len(),
+            users.len()
+        );
+        selected
+    }
+
+    /// [`find_matches`](Self::find_matches) as `(i, j, score)` index tuples
+    /// into `users`, for callers keeping their own parallel arrays.
+    ///
+    /// Returns the same pairs in the same order, with `users[i]` being the
+    /// match's `user1_id` side.
+    pub fn find_match_indices(&self, users: &[User]) -> Vec<(usize, usize, f64)> {
+        self.greedy_indices(users, &self.calculate_all_pairs(users))
+    }
+
+    /// Finds the matching with the highest possible total score.
+    ///
+    /// Uses the blossom algorithm in O(n³), so it is noticeably slower than
+    /// greedy on large pools but never settles for a locally good pair that
+    /// forces poor pairings elsewhere.
+    pub fn find_matches_optimal(&self, users: &[User]) -> Vec<Match> {
+        let pairs = self.calculate_all_pairs(users);
+        let weights = self.selection_weights(users, &pairs);
+        let mate = max_weight_matching(users.len(), &weights, true);
+        matches_from_mates(users, pairs, &mate)
+    }
+
+    /// Like [`find_matches`](Self::find_matches), but rotates who sits out
+    /// when the pool is odd.
+    ///
+    /// The matcher remembers how often each id has been left over. The user
+    /// greedy would naturally drop is kept out only if nobody has sat out
+    /// fewer times; otherwise the least-dropped user (latest in the slice on
+    /// ties) sits out and the rest are matched greedily.
+    pub fn find_matches_rotating(&mut self, users: &[User]) -> Vec<Match> {
+        let matches = self.find_matches_lenient(users);
+        if users.len().is_multiple_of(2) {
+            return matches;
+        }
+        let Some(natural) = unmatched_users(users, &matches)
+            .first()
+            .map(|u| u.id.clone())
+        else {
+            return matches;
+        };
+
+        let drops = |id: &str| self.drop_counts.get(id).copied().unwrap_or(0);
+        let fewest = users.iter().map(|u| drops(&u.id)).min().unwrap_or(0);
+        let leftover = if drops(&natural) == fewest {
+            natural.clone()
+        } else {
+            users
+                .iter()
+                .rev()
+                .find(|u| drops(&u.id) == fewest)
+                .map(|u| u.id.clone())
+                .unwrap_or_else(|| natural.clone())
+        };
+        *self.drop_counts.entry(leftover.clone()).or_insert(0) += 1;
+
+        if leftover == natural {
+            return matches;
+        }
+        let remaining: Vec<User> = users.iter().filter(|u| u.id != leftover).cloned().collect();
+        self.find_matches_lenient(&remaining)
+    }
+
+    /// How many times each id has been left over by
+    /// [`find_matches_rotating`](Self::find_matches_rotating).
+    pub fn drop_counts(&self) -> &HashMap<String, usize> {
+        &self.drop_counts
+    }
+
+    /// Greedy matching for recurring rounds where last round's leftover goes
+    /// first.
+    ///
+    /// Each user left unmatched by the previous call who is in `users` is
+    /// paired, in turn, with their highest-scoring partner still free before
+    /// anyone else is considered, so nobody sits out twice in a row as long
+    /// as they have a scorable partner (see
+    /// [`with_min_engagement`](Self::with_min_engagement) and
+    /// [`with_opposition_floor`](Self::with_opposition_floor)). The rest are
+    /// matched greedily, and everyone left over this time is remembered for
+    /// the next call. Errors only if the forced pairs can't be locked, as
+    /// for [`find_matches_with_fixed`](Self::find_matches_with_fixed).
+    pub fn find_matches_next_round(&mut self, users: &[User]) -> Result<Vec<Match>, NemesisError> {
+        let pairs = self.calculate_all_pairs(users);
+        let ranked = self.rank_pairs(users, &pairs);
+        let mut locked: HashSet<&str> = HashSet::new();
+        let mut forced = Vec::new();
+        for id in &self.last_leftovers {
+            if locked.contains(id.as_str()) {
+                continue;
+            }
+            let best = ranked.iter().find(|&&(i, j, _)| {
+                let (a, b) = (users[i].id.as_str(), users[j].id.as_str());
+                a != b && (a == id || b == id) && !locked.contains(a) && !locked.contains(b)
+            });
+            if let Some(&(i, j, _)) = best {
+                locked.extend([users[i].id.as_str(), users[j].id.as_str()]);
+                forced.push(constraints::ordered_pair(
+                    users[i].id.clone(),
+                    users[j].id.clone(),
+                ));
+            }
+        }
+        let matches = if forced.is_empty() {
+            self.greedy_select(users, pairs)
+        } else {
+            self.find_matches_with_fixed(users, &forced)?
+        };
+        self.last_leftovers = unmatched_users(users, &matches)
+            .into_iter()
+            .map(|u| u.id.clone())
+            .collect();
+        Ok(matches)
+    }
+
+    /// The users left unmatched by the last
+    /// [`find_matches_next_round`](Self::find_matches_next_round) call, in
+    /// pool order.
+    pub fn last_leftovers(&self) -> &[String] {
+        &self.last_leftovers
+    }
+}
+
+/// The two users with the smaller id first.
+fn id_order<'a>(user1: &'a User, user2: &'a User) -> (&'a User, &'a User) {
+    if user1.id <= user2.id {
+        (user1, user2)
+    } else {
+        (user2, user1)
+    }
+}
+
+/// Turns a blossom mate vector back into matches, scored from `pairs`.
+fn matches_from_mates(
+    users: &[User],
+    pairs: Vec<(usize, usize, f64)>,
+    mate: &[Option<usize>],
+) -> Vec<Match> {
+    let scores: HashMap<(usize, usize), f64> =
+        pairs.into_iter().map(|(i, j, s)| ((i, j), s)).collect();
+    debug_log!(
+        "{} of {} users left unmatched",
+        mate.iter().filter(|m| m.is_none()).count(),
+        users.len()
+    );
+    mate.iter()
+        .enumerate()
+        .filter_map(|(i, m)| m.filter(|&j| i < j).map(|j| (i, j)))
+        .map(|(i, j)| Match::new(users[i].id.clone(), users[j].id.clone(), scores[&(i, j)]))
+        .collect()
+}
+
+/// Users from `users` that appear in none of `matches`.
+pub fn unmatched_users<'a>(users: &'a [User], matches: &[Match]) -> Vec<&'a User> {
+    let matched: HashSet<&str> = matches
+        .iter()
+        .flat_map(|m| [m.user1_id.as_str(), m.user2_id.as_str()])
+        .collect();
+    users
+        .iter()
+        .filter(|u| !matched.contains(u.id.as_str()))
+        .collect()
+}
@@ -0,0 +1,44 @@
+use crate::matcher::{Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    /// The single most opposed member of `pool` for `target` ("your nemesis").
+    ///
+    /// Pool entries sharing the target's id are skipped, so the pool may
+    /// include the target. Ties go to whoever appears first. Returns `None`
+    /// when nobody else is in the pool.
+    pub fn best_opponent(&self, target: &User, pool: &[User]) -> Option<Match> {
+        let mut best: Option<Match> = None;
+        for other in pool.iter().filter(|u| u.id != target.id) {
+            let score = self.scorer.calculate_score(target, other);
+            if best.as_ref().is_none_or(|b| score > b.score) {
+                best = Some(Match::new(target.id.clone(), other.id.clone(), score));
+            }
+        }
+        best
+    }
+
+    /// The most and least opposed members of `pool` for `target` ("your
+    /// nemesis" and "your twin"), found in one pass.
+    ///
+    /// Same-id entries are skipped as in [`best_opponent`](Self::best_opponent),
+    /// and the nemesis is exactly what that returns. Ties for the twin go to
+    /// whoever appears last, so with two or more others in the pool the two
+    /// are different people even if everyone scores alike. Both are `None`
+    /// when nobody else is in the pool.
+    pub fn extremes_for(&self, target: &User, pool: &[User]) -> (Option<Match>, Option<Match>) {
+        let mut nemesis: Option<Match> = None;
+        let mut twin: Option<Match> = None;
+        for other in pool.iter().filter(|u| u.id != target.id) {
+            let score = self.scorer.calculate_score(target, other);
+            if nemesis.as_ref().is_none_or(|n| score > n.score) {
+                nemesis = Some(Match::new(target.id.clone(), other.id.clone(), score));
+            }
+            if twin.as_ref().is_none_or(|t| score <= t.score) {
+                twin = Some(Match::new(target.id.clone(), other.id.clone(), score));
+            }
+        }
+        (nemesis, twin)
+    }
+}
@@ -0,0 +1,35 @@
+use crate::analysis::controversy_scores;
+use crate::user::User;
+
+/// Splits a pool into `num_pools` sub-pools of near-equal size and
+/// comparable internal diversity, so each can be matched independently.
+///
+/// Users are ranked by [`controversy_scores`] (most polarizing first, input
+/// order on ties) and dealt out in a snake order: ranks `0..k` go to pools
+/// `0..k`, the next `k` ranks go to pools `k-1..=0`, and so on. Every pool
+/// therefore receives a similar mix of extreme and moderate users rather than
+/// one pool collecting all the firebrands. Pool sizes differ by at most one.
+///
+/// Returns no pools when `num_pools` is zero; if there are more pools than
+/// users, the surplus pools are empty.
+pub fn partition_pool(users: &[User], num_pools: usize) -> Vec<Vec<User>> {
+    if num_pools == 0 {
+        return Vec::new();
+    }
+    let controversy = controversy_scores(users);
+    let mut ranked: Vec<&User> = users.iter().collect();
+    ranked.sort_by(|a, b| controversy[&b.id].total_cmp(&controversy[&a.id]));
+
+    let mut pools: Vec<Vec<User>> = vec![Vec::new(); num_pools];
+    for (rank, user) in ranked.into_iter().enumerate() {
+        let round = rank / num_pools;
+        let offset = rank % num_pools;
+        let pool = if round.is_multiple_of(2) {
+            offset
+        } else {
+            num_pools - 1 - offset
+        };
+        pools[pool].push(user.clone());
+    }
+    pools
+}
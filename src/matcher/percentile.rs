@@ -0,0 +1,19 @@
+use crate::matcher::Match;
+
+/// Sets each match's [`percentile`](Match::percentile) relative to
+/// `all_pair_scores`, typically every pair score in the pool.
+///
+/// A match's percentile is the share of pool scores at or below its own,
+/// times 100, so the most opposed pair in the pool is at 100. Matches are
+/// left unlabelled when `all_pair_scores` is empty.
+pub fn label_matches(matches: &mut [Match], all_pair_scores: &[f64]) {
+    if all_pair_scores.is_empty() {
+        return;
+    }
+    let mut sorted = all_pair_scores.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    for m in matches {
+        let at_or_below = sorted.partition_point(|&s| s <= m.score);
+        m.percentile = Some(100.0 * at_or_below as f64 / sorted.len() as f64);
+    }
+}
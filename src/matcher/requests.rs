@@ -0,0 +1,53 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::matcher::{Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    /// Matches with users' requested opponents honored where possible.
+    ///
+    /// Each `(requester, requested)` entry asks for that pairing. Mutual
+    /// requests are honored first, then one-sided ones in list order, each
+    /// only if both users are in `users` and not already claimed by an
+    /// earlier request; so when two people ask for the same opponent, the
+    /// earlier entry wins. Honored requests come first in the result, with
+    /// the requester as `user1_id`, scored for the record but kept regardless
+    /// of score. Everyone else is then matched greedily. Requests naming
+    /// unknown ids, or the requester themselves, are ignored.
+    pub fn find_matches_with_requests(
+        &self,
+        users: &[User],
+        requests: &[(String, String)],
+    ) -> Vec<Match> {
+        let by_id: HashMap<&str, &User> = users.iter().map(|u| (u.id.as_str(), u)).collect();
+        let asked: HashSet<(&str, &str)> = requests
+            .iter()
+            .map(|(a, b)| (a.as_str(), b.as_str()))
+            .collect();
+        let is_mutual = |(a, b): &&(String, String)| asked.contains(&(b.as_str(), a.as_str()));
+        let (mutual, one_sided): (Vec<_>, Vec<_>) = requests.iter().partition(is_mutual);
+
+        let mut claimed: HashSet<&str> = HashSet::new();
+        let mut matches = Vec::with_capacity(users.len() / 2);
+        for (a, b) in mutual.into_iter().chain(one_sided) {
+            let (Some(u1), Some(u2)) = (by_id.get(a.as_str()), by_id.get(b.as_str())) else {
+                continue;
+            };
+            if a == b || claimed.contains(a.as_str()) || claimed.contains(b.as_str()) {
+                continue;
+            }
+            claimed.insert(&u1.id);
+            claimed.insert(&u2.id);
+            matches.push(Match::new(a.clone(), b.clone(), self.pair_score(u1, u2)));
+        }
+
+        let rest: Vec<User> = users
+            .iter()
+            .filter(|u| !claimed.contains(u.id.as_str()))
+            .cloned()
+            .collect();
+        matches.extend(self.find_matches_lenient(&rest));
+        matches
+    }
+}
@@ -0,0 +1,44 @@
+use crate::matcher::{Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// Orderings offered by [`NemesisMatcher::find_matches_sorted`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// Most opposed pairs first.
+    ScoreDesc,
+    /// Least opposed pairs first.
+    ScoreAsc,
+    /// Alphabetical by the first user's id.
+    User1Id,
+}
+
+/// Sorts matches in place. Ties fall back to the id pair so the order is total.
+pub fn sort_matches(matches: &mut [Match], by: SortKey) {
+    fn ids(m: &Match) -> (&str, &str) {
+        (&m.user1_id, &m.user2_id)
+    }
+    match by {
+        SortKey::ScoreDesc => matches.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| ids(a).cmp(&ids(b)))
+        }),
+        SortKey::ScoreAsc => matches.sort_by(|a, b| {
+            a.score
+                .total_cmp(&b.score)
+                .then_with(|| ids(a).cmp(&ids(b)))
+        }),
+        SortKey::User1Id => matches.sort_by(|a, b| ids(a).cmp(&ids(b))),
+    }
+}
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    /// [`find_matches`](Self::find_matches), returned in the requested order
+    /// instead of greedy-selection order.
+    pub fn find_matches_sorted(&self, users: &[User], by: SortKey) -> Vec<Match> {
+        let mut matches = self.find_matches_lenient(users);
+        sort_matches(&mut matches, by);
+        matches
+    }
+}
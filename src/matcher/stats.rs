@@ -0,0 +1,60 @@
+use crate::matcher::NemesisMatcher;
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    /// Mean opposition over every unique pair in the pool (a diversity index).
+    ///
+    /// Pools with fewer than two users have no pairs and score 0.
+    pub fn pool_diversity(&self, users: &[User]) -> f64 {
+        let pairs = self.calculate_all_pairs(users);
+        if pairs.is_empty() {
+            return 0.0;
+        }
+        pairs.iter().map(|&(_, _, s)| s).sum::<f64>() / pairs.len() as f64
+    }
+
+    /// Distribution of every pairwise score as `(bin_low, bin_high, count)`.
+    ///
+    /// The `bins` equal-width bins span the observed score range; each bin is
+    /// half-open except the last, which also holds the maximum score. When
+    /// every pair scores the same, a single zero-width bin holds them all.
+    /// Non-finite scores are left out. Returns nothing for `bins == 0` or a
+    /// pool with fewer than two users.
+    pub fn score_histogram(&self, users: &[User], bins: usize) -> Vec<(f64, f64, usize)> {
+        let scores: Vec<f64> = self
+            .calculate_all_pairs(users)
+            .into_iter()
+            .map(|(_, _, s)| s)
+            .filter(|s| s.is_finite())
+            .collect();
+        if bins == 0 || scores.is_empty() {
+            return Vec::new();
+        }
+        let low = scores.iter().copied().fold(f64::INFINITY, f64::min);
+        let high = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        if low == high {
+            return vec![(low, high, scores.len())];
+        }
+
+        let width = (high - low) / bins as f64;
+        let mut counts = vec![0usize; bins];
+        for s in scores {
+            let bin = (((s - low) / width) as usize).min(bins - 1);
+            counts[bin] += 1;
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(b, count)| {
+                let bin_low = low + width * b as f64;
+                let bin_high = if b + 1 == bins {
+                    high
+                } else {
+                    low + width * (b + 1) as f64
+                };
+                (bin_low, bin_high, count)
+            })
+            .collect()
+    }
+}
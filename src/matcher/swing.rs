@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+
+use crate::matcher::constraints::ordered_pair;
+use crate::matcher::NemesisMatcher;
+use crate::scoring::ScoringStrategy;
+use crate::user::{User, NEUTRAL_RESPONSE};
+
+/// For each question, how many greedy pairs change when it is left out.
+///
+/// Returns `(question_index, broken_pairs)` in question order, where
+/// `broken_pairs` counts pairs of the baseline matching that don't survive
+/// once that question is ablated. Ablation sets everyone's answer to the
+/// neutral midpoint rather than deleting the column, so per-question weights
+/// stay aligned and difference-based scorers see a zero contribution. This
+/// runs the matcher once per question.
+pub fn swing_questions<S: ScoringStrategy>(users: &[User], scorer: &S) -> Vec<(usize, usize)> {
+    let matcher = NemesisMatcher::new(scorer);
+    let baseline = top_pairs(&matcher, users, usize::MAX);
+    let num_questions = users.iter().map(User::num_questions).max().unwrap_or(0);
+
+    (0..num_questions)
+        .map(|q| {
+            let ablated = ablate(users, &HashSet::from([q]));
+            let pairs = top_pairs(&matcher, &ablated, usize::MAX);
+            (q, baseline.difference(&pairs).count())
+        })
+        .collect()
+}
+
+/// Questions to keep so the `keep_top` highest-scoring greedy matches stay
+/// the same, found by greedily dropping questions in index order.
+///
+/// Each question is dropped (ablated as in [`swing_questions`]) if the top
+/// matches are unchanged with it and every earlier dropped question gone,
+/// so the result is small but not guaranteed minimal. Returns the retained
+/// question indices in ascending order.
+pub fn minimal_question_subset<S: ScoringStrategy>(
+    users: &[User],
+    scorer: &S,
+    keep_top: usize,
+) -> Vec<usize> {
+    let matcher = NemesisMatcher::new(scorer);
+    let baseline = top_pairs(&matcher, users, keep_top);
+    let num_questions = users.iter().map(User::num_questions).max().unwrap_or(0);
+
+    let mut dropped = HashSet::new();
+    for q in 0..num_questions {
+        dropped.insert(q);
+        if top_pairs(&matcher, &ablate(users, &dropped), keep_top) != baseline {
+            dropped.remove(&q);
+        }
+    }
+    (0..num_questions)
+        .filter(|q| !dropped.contains(q))
+        .collect()
+}
+
+/// The first `limit` greedy matches as order-independent id pairs.
+fn top_pairs<S: ScoringStrategy>(
+    matcher: &NemesisMatcher<S>,
+    users: &[User],
+    limit: usize,
+) -> HashSet<(String, String)> {
+    matcher
+        .find_matches_lenient(users)
+        .into_iter()
+        .take(limit)
+        .map(|m| ordered_pair(m.user1_id, m.user2_id))
+        .collect()
+}
+
+/// Copies of `users` with every question in `questions` set to neutral.
+fn ablate(users: &[User], questions: &HashSet<usize>) -> Vec<User> {
+    users
+        .iter()
+        .map(|u| {
+            let responses = u
+                .responses
+                .iter()
+                .enumerate()
+                .map(|(q, &r)| {
+                    if questions.contains(&q) {
+                        NEUTRAL_RESPONSE
+                    } else {
+                        r
+                    }
+                })
+                .collect();
+            u.with_responses(responses)
+        })
+        .collect()
+}
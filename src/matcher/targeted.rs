@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use crate::error::NemesisError;
+use crate::matcher::{Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    /// Scores just the listed id pairs instead of the whole O(n²) matrix.
+    ///
+    /// Results come back in the order of `pairs`, each oriented as given
+    /// though scored with the smaller id first.
+    /// Errors with [`NemesisError::UnknownUserId`] on the first id that isn't
+    /// in `users`.
+    pub fn score_pairs(
+        &self,
+        users: &[User],
+        pairs: &[(String, String)],
+    ) -> Result<Vec<Match>, NemesisError> {
+        let by_id: HashMap<&str, &User> = users.iter().map(|u| (u.id.as_str(), u)).collect();
+        let lookup = |id: &str| {
+            by_id
+                .get(id)
+                .copied()
+                .ok_or_else(|| NemesisError::UnknownUserId(id.to_string()))
+        };
+        pairs
+            .iter()
+            .map(|(a, b)| {
+                let (u1, u2) = (lookup(a)?, lookup(b)?);
+                Ok(Match::new(a.clone(), b.clone(), self.pair_score(u1, u2)))
+            })
+            .collect()
+    }
+
+    /// Scores every newcomer against every member of a fixed roster.
+    ///
+    /// `grid[i][j]` is the score of `newcomers[i]` against `roster[j]`.
+    /// Unlike
+    /// [`calculate_all_pairs`](Self::calculate_all_pairs), newcomers aren't
+    /// scored against each other, nor roster members against each other.
+    /// Each pair is scored with the smaller id first, as everywhere else.
+    pub fn score_against_roster(&self, newcomers: &[User], roster: &[User]) -> Vec<Vec<f64>> {
+        newcomers
+            .iter()
+            .map(|newcomer| {
+                roster
+                    .iter()
+                    .map(|host| self.pair_score(newcomer, host))
+                    .collect()
+            })
+            .collect()
+    }
+}
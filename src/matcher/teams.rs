@@ -0,0 +1,38 @@
+use crate::matcher::blossom::max_weight_matching;
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// Pairs up pre-formed blocks of users (teams) as units.
+///
+/// Two blocks are scored by the mean `scorer` score over every cross-block
+/// pair of members, so block size doesn't inflate the score. Blocks are then
+/// matched to maximise total opposition, leaving one block out if there is
+/// an odd number of them. Returns `(i, j, score)` with block indices
+/// `i < j`, ordered by `i`. A block with no members scores 0 against
+/// everyone.
+pub fn match_blocks<S: ScoringStrategy>(
+    blocks: &[Vec<User>],
+    scorer: &S,
+) -> Vec<(usize, usize, f64)> {
+    let block_score = |a: &[User], b: &[User]| {
+        if a.is_empty() || b.is_empty() {
+            return 0.0;
+        }
+        let total: f64 = a
+            .iter()
+            .flat_map(|u1| b.iter().map(move |u2| scorer.calculate_score(u1, u2)))
+            .sum();
+        total / (a.len() * b.len()) as f64
+    };
+    let mut edges = Vec::with_capacity(blocks.len() * blocks.len().saturating_sub(1) / 2);
+    for i in 0..blocks.len() {
+        for j in (i + 1)..blocks.len() {
+            edges.push((i, j, block_score(&blocks[i], &blocks[j])));
+        }
+    }
+    let mate = max_weight_matching(blocks.len(), &edges, true);
+    edges
+        .into_iter()
+        .filter(|&(i, j, _)| mate[i] == Some(j))
+        .collect()
+}
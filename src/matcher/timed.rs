@@ -0,0 +1,56 @@
+use std::time::{Duration, Instant};
+
+use crate::matcher::blossom::max_weight_matching_until;
+use crate::matcher::{matches_from_mates, Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// Result of [`NemesisMatcher::find_matches_within`], recording which
+/// algorithm produced the matches.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchOutcome {
+    /// The optimal matcher finished inside the budget.
+    Optimal(Vec<Match>),
+    /// The budget ran out, so these are the greedy matches instead.
+    GreedyFallback(Vec<Match>),
+}
+
+impl MatchOutcome {
+    pub fn matches(&self) -> &[Match] {
+        match self {
+            MatchOutcome::Optimal(matches) | MatchOutcome::GreedyFallback(matches) => matches,
+        }
+    }
+
+    pub fn into_matches(self) -> Vec<Match> {
+        match self {
+            MatchOutcome::Optimal(matches) | MatchOutcome::GreedyFallback(matches) => matches,
+        }
+    }
+
+    pub fn is_optimal(&self) -> bool {
+        matches!(self, MatchOutcome::Optimal(_))
+    }
+}
+
+impl<S: ScoringStrategy> NemesisMatcher<S> {
+    /// [`find_matches_optimal`](Self::find_matches_optimal) with a latency cap.
+    ///
+    /// The budget covers pair scoring and the blossom search. If it runs out,
+    /// the already-scored pairs are matched greedily instead, so the call
+    /// overruns `max_millis` by at most one blossom step plus the greedy pass.
+    pub fn find_matches_within(&self, users: &[User], max_millis: u64) -> MatchOutcome {
+        let deadline = Instant::now() + Duration::from_millis(max_millis);
+        let pairs = self.calculate_all_pairs(users);
+        let mate = if Instant::now() < deadline {
+            let weights = self.selection_weights(users, &pairs);
+            max_weight_matching_until(users.len(), &weights, true, deadline)
+        } else {
+            None
+        };
+        match mate {
+            Some(mate) => MatchOutcome::Optimal(matches_from_mates(users, pairs, &mate)),
+            None => MatchOutcome::GreedyFallback(self.greedy_select(users, pairs)),
+        }
+    }
+}
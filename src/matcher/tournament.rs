@@ -0,0 +1,65 @@
+use std::collections::HashSet;
+
+use crate::matcher::blossom::max_weight_matching;
+use crate::matcher::constraints::ordered_pair;
+use crate::matcher::{matches_from_mates, Match, NemesisMatcher};
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// Generates successive tournament rounds in which no pair ever meets twice.
+///
+/// Each round is a maximum-cardinality, maximum-opposition matching over the
+/// pairs that haven't met yet, so later rounds may leave more people out as
+/// fresh pairings run short. Once no unplayed pair remains,
+/// [`next_round`](Self::next_round) returns an empty round.
+#[derive(Debug, Clone)]
+pub struct TournamentScheduler<S: ScoringStrategy> {
+    matcher: NemesisMatcher<S>,
+    played: HashSet<(String, String)>,
+    rounds: usize,
+}
+
+impl<S: ScoringStrategy> TournamentScheduler<S> {
+    pub fn new(scorer: S) -> Self {
+        TournamentScheduler {
+            matcher: NemesisMatcher::new(scorer),
+            played: HashSet::new(),
+            rounds: 0,
+        }
+    }
+
+    /// Plays the next round and records its pairings.
+    ///
+    /// An empty result means the tournament is exhausted for `users`; it
+    /// doesn't count as a round.
+    pub fn next_round(&mut self, users: &[User]) -> Vec<Match> {
+        let fresh: Vec<(usize, usize, f64)> = self
+            .matcher
+            .calculate_all_pairs(users)
+            .into_iter()
+            .filter(|&(i, j, _)| !self.has_played(&users[i].id, &users[j].id))
+            .collect();
+        if fresh.is_empty() {
+            return Vec::new();
+        }
+        let mate = max_weight_matching(users.len(), &fresh, true);
+        let round = matches_from_mates(users, fresh, &mate);
+        for m in &round {
+            self.played
+                .insert(ordered_pair(m.user1_id.clone(), m.user2_id.clone()));
+        }
+        self.rounds += 1;
+        round
+    }
+
+    /// Whether `a` and `b` have already met in an earlier round.
+    pub fn has_played(&self, a: &str, b: &str) -> bool {
+        self.played
+            .contains(&ordered_pair(a.to_string(), b.to_string()))
+    }
+
+    /// Number of non-empty rounds generated so far.
+    pub fn rounds_played(&self) -> usize {
+        self.rounds
+    }
+}
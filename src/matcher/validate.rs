@@ -0,0 +1,37 @@
+use std::collections::HashSet;
+
+use crate::error::NemesisError;
+use crate::matcher::{unmatched_users, Match};
+use crate::user::User;
+
+/// Checks that `matches` is a perfect matching of `users`, e.g. after an
+/// organizer edited it by hand.
+///
+/// Every id in a match must belong to `users`
+/// ([`NemesisError::UnknownUserId`]) and appear in only one match, including
+/// not being matched with itself ([`NemesisError::DuplicateUserId`]). Every
+/// user must be covered except for at most one leftover, which an odd pool
+/// needs ([`NemesisError::IncompleteMatching`]). The first violation found
+/// is reported.
+pub fn validate_matching(matches: &[Match], users: &[User]) -> Result<(), NemesisError> {
+    let known: HashSet<&str> = users.iter().map(|u| u.id.as_str()).collect();
+    let mut seen: HashSet<&str> = HashSet::new();
+    for id in matches
+        .iter()
+        .flat_map(|m| [m.user1_id.as_str(), m.user2_id.as_str()])
+    {
+        if !known.contains(id) {
+            return Err(NemesisError::UnknownUserId(id.to_string()));
+        }
+        if !seen.insert(id) {
+            return Err(NemesisError::DuplicateUserId(id.to_string()));
+        }
+    }
+    let unmatched = unmatched_users(users, matches);
+    if unmatched.len() > users.len() % 2 {
+        return Err(NemesisError::IncompleteMatching {
+            unmatched: unmatched.iter().map(|u| u.id.clone()).collect(),
+        });
+    }
+    Ok(())
+}
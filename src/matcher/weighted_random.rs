@@ -0,0 +1,94 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::hash::{BuildHasher, Hasher};
+
+use crate::matcher::{id_order, Match, NemesisMatcher};
+use crate::rng::SplitMix64;
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// Samples matchings that favour opposed pairs without always picking the same ones.
+///
+/// Pairs are drawn one at a time from those whose members are both still
+/// free, each with probability proportional to `exp(score / temperature)`
+/// (a softmax over scores). Low temperatures approach greedy; high ones
+/// approach a uniformly random matching.
+///
+/// Without [`with_seed`](Self::with_seed) the generator is seeded from the
+/// OS, so every matcher produces different runs. With a seed, a fresh matcher
+/// always produces the same sequence of matchings.
+#[derive(Debug, Clone)]
+pub struct WeightedRandomMatcher<S: ScoringStrategy> {
+    matcher: NemesisMatcher<S>,
+    temperature: f64,
+    rng: SplitMix64,
+}
+
+impl<S: ScoringStrategy> WeightedRandomMatcher<S> {
+    pub fn new(scorer: S) -> Self {
+        let seed = RandomState::new().build_hasher().finish();
+        WeightedRandomMatcher {
+            matcher: NemesisMatcher::new(scorer),
+            temperature: 1.0,
+            rng: SplitMix64::new(seed),
+        }
+    }
+
+    /// Makes runs reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = SplitMix64::new(seed);
+        self
+    }
+
+    /// Sets the softmax temperature (default 1.0), in score units.
+    ///
+    /// Non-positive or NaN values are raised to the smallest positive float,
+    /// which makes sampling effectively greedy.
+    pub fn with_temperature(mut self, temperature: f64) -> Self {
+        self.temperature = if temperature > 0.0 {
+            temperature
+        } else {
+            f64::MIN_POSITIVE
+        };
+        self
+    }
+
+    pub fn temperature(&self) -> f64 {
+        self.temperature
+    }
+
+    /// Draws one matching. Every user is matched except one in an odd pool.
+    pub fn find_matches(&mut self, users: &[User]) -> Vec<Match> {
+        let mut remaining = self.matcher.calculate_all_pairs(users);
+        let mut matched: HashSet<usize> = HashSet::new();
+        let mut matches = Vec::with_capacity(users.len() / 2);
+        while !remaining.is_empty() {
+            let top = remaining
+                .iter()
+                .map(|p| p.2)
+                .fold(f64::NEG_INFINITY, f64::max);
+            let weights: Vec<f64> = remaining
+                .iter()
+                .map(|p| ((p.2 - top) / self.temperature).exp())
+                .collect();
+            let total: f64 = weights.iter().sum();
+            let mut target = self.rng.next_f64() * total;
+            let mut pick = remaining.len() - 1;
+            for (k, w) in weights.iter().enumerate() {
+                if target < *w {
+                    pick = k;
+                    break;
+                }
+                target -= w;
+            }
+
+            let (i, j, score) = remaining[pick];
+            let (u1, u2) = id_order(&users[i], &users[j]);
+            matches.push(Match::new(u1.id.clone(), u2.id.clone(), score));
+            matched.insert(i);
+            matched.insert(j);
+            remaining.retain(|&(a, b, _)| !matched.contains(&a) && !matched.contains(&b));
+        }
+        matches
+    }
+}
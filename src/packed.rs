@@ -0,0 +1,97 @@
+//! A compact storage form for users.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::NemesisError;
+use crate::user::{deserialize_id, validate_id, validate_responses, User};
+
+/// URL-safe base64 alphabet; each character carries 6 bits.
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// A [`User`] whose answers are packed into a short string for storage.
+///
+/// Answers on the 1-7 scale fit in 3 bits, so each character of `responses`
+/// holds two of them: 25 answers become 13 characters instead of a 25-element
+/// JSON array. The alphabet is URL-safe base64. A 0 in the low half of the
+/// final character pads an odd count, which is unambiguous because 0 is never
+/// a valid answer. Only the id, answers and tags are kept; confidence and
+/// stars are dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackedUser {
+    #[serde(deserialize_with = "deserialize_id")]
+    pub id: String,
+    pub responses: String,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tags: HashMap<String, String>,
+}
+
+impl PackedUser {
+    /// Packs `user`, whose answers must be on the 1-7 scale.
+    pub fn pack(user: &User) -> Result<Self, NemesisError> {
+        validate_responses(&user.responses)?;
+        let responses = user
+            .responses
+            .chunks(2)
+            .map(|pair| {
+                let high = pair[0] as usize;
+                let low = pair.get(1).map_or(0, |&r| r as usize);
+                ALPHABET[(high << 3) | low] as char
+            })
+            .collect();
+        Ok(PackedUser {
+            id: user.id.clone(),
+            responses,
+            tags: user.tags.clone(),
+        })
+    }
+
+    /// Unpacks and re-validates the id and answers.
+    ///
+    /// Errors with [`NemesisError::Deserialization`] on a character outside
+    /// the alphabet or misplaced padding, and with the usual id and response
+    /// errors if the id is blank or a decoded answer is off the scale.
+    pub fn unpack(&self) -> Result<User, NemesisError> {
+        let id = validate_id(self.id.clone())?;
+        let chars = self.responses.as_bytes();
+        let mut responses = Vec::with_capacity(chars.len() * 2);
+        for (position, &c) in chars.iter().enumerate() {
+            let bits = ALPHABET.iter().position(|&a| a == c).ok_or_else(|| {
+                NemesisError::Deserialization(format!(
+                    "invalid character {:?} in packed responses",
+                    c as char
+                ))
+            })? as i32;
+            let (high, low) = (bits >> 3, bits & 7);
+            responses.push(high);
+            if low != 0 {
+                responses.push(low);
+            } else if position + 1 != chars.len() {
+                return Err(NemesisError::Deserialization(
+                    "padding before the end of packed responses".to_string(),
+                ));
+            }
+        }
+        validate_responses(&responses)?;
+        let mut user = User::unchecked(id, responses);
+        user.tags = self.tags.clone();
+        Ok(user)
+    }
+}
+
+impl TryFrom<&User> for PackedUser {
+    type Error = NemesisError;
+
+    fn try_from(user: &User) -> Result<Self, Self::Error> {
+        PackedUser::pack(user)
+    }
+}
+
+impl TryFrom<&PackedUser> for User {
+    type Error = NemesisError;
+
+    fn try_from(packed: &PackedUser) -> Result<Self, Self::Error> {
+        packed.unpack()
+    }
+}
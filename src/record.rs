@@ -0,0 +1,38 @@
+//! Reproducible records of matching runs, for auditing.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::NemesisError;
+use crate::evaluation::total_score;
+use crate::matcher::{Match, NemesisMatcher};
+use crate::scoring::StrategyConfig;
+use crate::user::User;
+
+/// Everything needed to audit or replay one greedy matching run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchingRun {
+    /// Ids of every input user, in input order.
+    pub user_ids: Vec<String>,
+    /// The strategy that scored the pairs.
+    pub config: StrategyConfig,
+    pub matches: Vec<Match>,
+    /// Sum of the match scores at the time of the run.
+    pub total_score: f64,
+}
+
+/// Builds the scorer from `config`, matches `users` greedily and records the run.
+///
+/// Fails only if `config` doesn't describe a valid scorer.
+pub fn run_and_record(
+    users: &[User],
+    config: &StrategyConfig,
+) -> Result<MatchingRun, NemesisError> {
+    let matcher = NemesisMatcher::new(config.build()?);
+    let matches = matcher.find_matches_lenient(users);
+    Ok(MatchingRun {
+        user_ids: users.iter().map(|u| u.id.clone()).collect(),
+        config: config.clone(),
+        total_score: total_score(&matches),
+        matches,
+    })
+}
@@ -0,0 +1,64 @@
+use crate::error::NemesisError;
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// How much question `question_index` adds to the pair's score under `scorer`.
+///
+/// Computed as the full score minus the score with that question silenced
+/// (both users given `user1`'s answer), so question positions and any
+/// per-question weights are preserved. For decomposable scorers such as
+/// [`SimpleDifferenceScorer`](crate::scoring::SimpleDifferenceScorer) or
+/// [`WeightedScorer`](crate::scoring::WeightedScorer) the marginals of all
+/// questions sum to the full score; for others (Euclidean, cosine) they are
+/// only a sensitivity estimate, so those are rejected with
+/// [`NemesisError::NotDecomposable`]. An index past either answer sheet
+/// contributes 0.
+pub fn marginal_contribution<S: ScoringStrategy + ?Sized>(
+    scorer: &S,
+    user1: &User,
+    user2: &User,
+    question_index: usize,
+) -> Result<f64, NemesisError> {
+    if !scorer.is_decomposable() {
+        return Err(NemesisError::NotDecomposable(scorer.name()));
+    }
+    Ok(marginal_unchecked(scorer, user1, user2, question_index))
+}
+
+/// [`marginal_contribution`] for a scorer already known to be decomposable.
+pub(crate) fn marginal_unchecked<S: ScoringStrategy + ?Sized>(
+    scorer: &S,
+    user1: &User,
+    user2: &User,
+    question_index: usize,
+) -> f64 {
+    let (Some(&a), Some(_)) = (
+        user1.responses.get(question_index),
+        user2.responses.get(question_index),
+    ) else {
+        return 0.0;
+    };
+    let mut silenced = user2.responses.clone();
+    silenced[question_index] = a;
+    scorer.calculate_score(user1, user2)
+        - scorer.calculate_score(user1, &user2.with_responses(silenced))
+}
+
+/// [`marginal_contribution`] for every question the pair both answered.
+///
+/// Errors with [`NemesisError::NotDecomposable`] unless the scorer
+/// [is decomposable](ScoringStrategy::is_decomposable), since otherwise the
+/// parts wouldn't add up to the score.
+pub fn score_breakdown<S: ScoringStrategy + ?Sized>(
+    scorer: &S,
+    user1: &User,
+    user2: &User,
+) -> Result<Vec<f64>, NemesisError> {
+    if !scorer.is_decomposable() {
+        return Err(NemesisError::NotDecomposable(scorer.name()));
+    }
+    let n = user1.num_questions().min(user2.num_questions());
+    Ok((0..n)
+        .map(|q| marginal_unchecked(scorer, user1, user2, q))
+        .collect())
+}
@@ -0,0 +1,89 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::error::NemesisError;
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// Memoizes another scorer's pair scores by `(user1.id, user2.id)`.
+///
+/// Repeated matcher runs over the same pool then score each pair only once.
+/// The cache assumes users are immutable: if a user's answers change under
+/// the same id, the stale score is still returned, so call
+/// [`clear`](Self::clear) (or build a new wrapper) after editing users. Keys
+/// keep argument order, so asymmetric scorers are cached correctly. Only
+/// [`calculate_score`](ScoringStrategy::calculate_score) is cached; the other
+/// methods go straight to `inner`.
+#[derive(Debug, Clone, Default)]
+pub struct CachedScorer<S> {
+    pub inner: S,
+    cache: RefCell<HashMap<(String, String), f64>>,
+}
+
+impl<S: ScoringStrategy> CachedScorer<S> {
+    pub fn new(inner: S) -> Self {
+        CachedScorer {
+            inner,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Number of pair scores currently memoized.
+    pub fn cached_pairs(&self) -> usize {
+        self.cache.borrow().len()
+    }
+
+    /// Forgets every memoized score.
+    pub fn clear(&self) {
+        self.cache.borrow_mut().clear();
+    }
+}
+
+impl<S: ScoringStrategy> ScoringStrategy for CachedScorer<S> {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        let key = (user1.id.clone(), user2.id.clone());
+        if let Some(&score) = self.cache.borrow().get(&key) {
+            return score;
+        }
+        let score = self.inner.calculate_score(user1, user2);
+        self.cache.borrow_mut().insert(key, score);
+        score
+    }
+
+    /// Forwards to `inner` without caching.
+    fn try_calculate_score(&self, user1: &User, user2: &User) -> Result<f64, NemesisError> {
+        self.inner.try_calculate_score(user1, user2)
+    }
+
+    /// The inner scorer's name; caching doesn't change what is computed.
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn is_decomposable(&self) -> bool {
+        self.inner.is_decomposable()
+    }
+
+    fn is_position_dependent(&self) -> bool {
+        self.inner.is_position_dependent()
+    }
+
+    /// Forwarded uncached: the default rescoring would reuse the same ids
+    /// with a changed answer and hit the stale cache entry.
+    fn answer_sensitivity(
+        &self,
+        user1: &User,
+        user2: &User,
+        index: usize,
+    ) -> Result<f64, NemesisError> {
+        self.inner.answer_sensitivity(user1, user2, index)
+    }
+
+    fn normalized_per_question(&self, user1: &User, user2: &User) -> f64 {
+        self.inner.normalized_per_question(user1, user2)
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        self.inner.score_bounds(num_questions)
+    }
+}
@@ -0,0 +1,70 @@
+use crate::error::NemesisError;
+use crate::scoring::breakdown::marginal_unchecked;
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// Limits how much any single question can add to another scorer's total.
+///
+/// Each question's term is taken from `inner` as its
+/// [`marginal_contribution`](crate::scoring::marginal_contribution), capped
+/// at `cap`, and the capped terms are summed, so one heavily weighted or
+/// naturally wide question can't swamp the rest. `inner` must be
+/// decomposable, as the terms wouldn't add up otherwise. Compare
+/// [`ClampScorer`](crate::scoring::ClampScorer), which caps the total.
+#[derive(Debug, Clone, Copy)]
+pub struct CapPerQuestion<S> {
+    inner: S,
+    cap: f64,
+}
+
+impl<S: ScoringStrategy> CapPerQuestion<S> {
+    /// Errors with [`NemesisError::InvalidWeights`] unless `cap` is finite
+    /// and non-negative, and with [`NemesisError::NotDecomposable`] unless
+    /// `inner` is decomposable.
+    pub fn new(inner: S, cap: f64) -> Result<Self, NemesisError> {
+        if !cap.is_finite() || cap < 0.0 {
+            return Err(NemesisError::InvalidWeights(format!(
+                "per-question cap {cap} must be finite and non-negative"
+            )));
+        }
+        if !inner.is_decomposable() {
+            return Err(NemesisError::NotDecomposable(inner.name()));
+        }
+        Ok(CapPerQuestion { inner, cap })
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn cap(&self) -> f64 {
+        self.cap
+    }
+}
+
+impl<S: ScoringStrategy> ScoringStrategy for CapPerQuestion<S> {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        let shared = user1.num_questions().min(user2.num_questions());
+        (0..shared)
+            .map(|q| marginal_unchecked(&self.inner, user1, user2, q).min(self.cap))
+            .sum()
+    }
+
+    fn name(&self) -> String {
+        format!("{}(cap {} per question)", self.inner.name(), self.cap)
+    }
+
+    fn is_decomposable(&self) -> bool {
+        self.inner.is_decomposable()
+    }
+
+    fn is_position_dependent(&self) -> bool {
+        self.inner.is_position_dependent()
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        let (low, high) = self.inner.score_bounds(num_questions);
+        let capped = self.cap * num_questions as f64;
+        (low.min(capped), high.min(capped))
+    }
+}
@@ -0,0 +1,102 @@
+use crate::scoring::ScoringStrategy;
+use crate::user::{User, MAX_RESPONSE, MIN_RESPONSE};
+
+/// Absolute difference measured around a circle, for wrap-around scales.
+///
+/// On a circular question (say, an angle on a political compass) the ends of
+/// the scale are neighbours, so the distance between answers `a` and `b` is
+/// `min(|a - b|, scale_size - |a - b|)`. With the default `scale_size` of 7,
+/// answers 1 and 7 are one step apart and the furthest apart any two answers
+/// can be is 3.
+///
+/// Every question is treated as circular unless
+/// [`with_circular_questions`](Self::with_circular_questions) flags only some
+/// of them; unflagged questions use the plain linear difference. An empty
+/// flag list therefore makes every question linear.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircularDifferenceScorer {
+    pub scale_size: u32,
+    /// `None` until flags are given, meaning every question wraps.
+    circular: Option<Vec<bool>>,
+}
+
+impl Default for CircularDifferenceScorer {
+    fn default() -> Self {
+        CircularDifferenceScorer::new((MAX_RESPONSE - MIN_RESPONSE + 1) as u32)
+    }
+}
+
+impl CircularDifferenceScorer {
+    /// A scorer treating every question as a circle of `scale_size` positions.
+    /// A `scale_size` of 0 disables wrapping.
+    pub fn new(scale_size: u32) -> Self {
+        CircularDifferenceScorer {
+            scale_size,
+            circular: None,
+        }
+    }
+
+    /// Limits wrapping to the questions flagged `true`; questions past the
+    /// end of `flags` are linear.
+    pub fn with_circular_questions(mut self, flags: Vec<bool>) -> Self {
+        self.circular = Some(flags);
+        self
+    }
+
+    /// Whether `question` is scored around the circle.
+    pub fn is_circular(&self, question: usize) -> bool {
+        match &self.circular {
+            None => true,
+            Some(flags) => flags.get(question).copied().unwrap_or(false),
+        }
+    }
+
+    fn distance(&self, question: usize, a: i32, b: i32) -> i64 {
+        let linear = (a as i64 - b as i64).abs();
+        let size = self.scale_size as i64;
+        if size == 0 || !self.is_circular(question) {
+            return linear;
+        }
+        let around = linear % size;
+        around.min(size - around)
+    }
+}
+
+impl ScoringStrategy for CircularDifferenceScorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        user1
+            .responses
+            .iter()
+            .zip(&user2.responses)
+            .enumerate()
+            .map(|(q, (&a, &b))| self.distance(q, a, b))
+            .sum::<i64>() as f64
+    }
+
+    fn name(&self) -> String {
+        format!("CircularDifference({})", self.scale_size)
+    }
+
+    /// Only once flags pick out particular questions.
+    fn is_position_dependent(&self) -> bool {
+        self.circular.is_some()
+    }
+
+    fn is_decomposable(&self) -> bool {
+        true
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        let span = (MAX_RESPONSE - MIN_RESPONSE) as i64;
+        let top: i64 = (0..num_questions)
+            .map(|q| {
+                if self.scale_size > 0 && self.is_circular(q) {
+                    span.min(self.scale_size as i64 / 2)
+                } else {
+                    span
+                }
+            })
+            .sum();
+        (0.0, top as f64)
+    }
+}
@@ -0,0 +1,49 @@
+use crate::error::NemesisError;
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// Caps another scorer's output at `max`.
+///
+/// Useful when a downstream algorithm assumes bounded edge weights and a
+/// scorer with custom multipliers can occasionally produce outsized scores.
+/// A NaN from `inner` is passed through rather than clamped, so the checked
+/// paths still reject it.
+#[derive(Debug, Clone, Copy)]
+pub struct ClampScorer<S> {
+    pub inner: S,
+    pub max: f64,
+}
+
+impl<S: ScoringStrategy> ClampScorer<S> {
+    pub fn new(inner: S, max: f64) -> Self {
+        ClampScorer { inner, max }
+    }
+}
+
+impl<S: ScoringStrategy> ScoringStrategy for ClampScorer<S> {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        let score = self.inner.calculate_score(user1, user2);
+        if score.is_nan() {
+            return score;
+        }
+        score.min(self.max)
+    }
+
+    /// Runs `inner`'s own checks before clamping.
+    fn try_calculate_score(&self, user1: &User, user2: &User) -> Result<f64, NemesisError> {
+        Ok(self.inner.try_calculate_score(user1, user2)?.min(self.max))
+    }
+
+    fn name(&self) -> String {
+        format!("{}(max {})", self.inner.name(), self.max)
+    }
+
+    fn is_position_dependent(&self) -> bool {
+        self.inner.is_position_dependent()
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        let (low, high) = self.inner.score_bounds(num_questions);
+        (low.min(self.max), high.min(self.max))
+    }
+}
@@ -0,0 +1,66 @@
+use crate::scoring::ScoringStrategy;
+use crate::user::{User, MAX_RESPONSE, MIN_RESPONSE};
+
+/// How [`ConfidenceScorer`] merges the two users' confidence in a question.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConfidenceCombine {
+    /// The less certain of the two answers sets the weight.
+    #[default]
+    Min,
+    /// Both uncertainties compound.
+    Product,
+}
+
+/// Absolute difference per question, scaled by how sure both users were.
+///
+/// Each question contributes `|a - b| · combine(conf1, conf2)`, using the
+/// per-answer [`User::confidence`] values (0.0 to 1.0). Users without
+/// recorded confidence count as fully certain, so for them this equals
+/// [`SimpleDifferenceScorer`](crate::scoring::SimpleDifferenceScorer).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfidenceScorer {
+    pub combine: ConfidenceCombine,
+}
+
+impl ConfidenceScorer {
+    pub fn new(combine: ConfidenceCombine) -> Self {
+        ConfidenceScorer { combine }
+    }
+}
+
+impl ScoringStrategy for ConfidenceScorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        user1
+            .responses
+            .iter()
+            .zip(&user2.responses)
+            .enumerate()
+            .map(|(q, (&a, &b))| {
+                let (c1, c2) = (user1.confidence_at(q), user2.confidence_at(q));
+                let weight = match self.combine {
+                    ConfidenceCombine::Min => c1.min(c2),
+                    ConfidenceCombine::Product => c1 * c2,
+                };
+                (a as f64 - b as f64).abs() * weight
+            })
+            .sum()
+    }
+
+    fn name(&self) -> String {
+        match self.combine {
+            ConfidenceCombine::Min => "Confidence(min)".to_string(),
+            ConfidenceCombine::Product => "Confidence(product)".to_string(),
+        }
+    }
+
+    fn is_decomposable(&self) -> bool {
+        true
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        (
+            0.0,
+            ((MAX_RESPONSE - MIN_RESPONSE) as usize * num_questions) as f64,
+        )
+    }
+}
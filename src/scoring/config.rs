@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::NemesisError;
+use crate::scoring::{
+    CosineSimilarityScorer, EuclideanDistanceScorer, PolarizationScorer, ScoringStrategy,
+    SimpleDifferenceScorer, WeightedCosineScorer, WeightedScorer,
+};
+
+/// Serializable description of a scoring strategy and its parameters.
+///
+/// Store this alongside a run to rebuild exactly the scorer that produced it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "strategy")]
+pub enum StrategyConfig {
+    SimpleDifference,
+    EuclideanDistance,
+    CosineSimilarity,
+    Weighted {
+        weights: Vec<f64>,
+    },
+    WeightedCosine {
+        weights: Vec<f64>,
+        neutral: f64,
+    },
+    Polarization {
+        extreme_multiplier: f64,
+        lean_multiplier: f64,
+        moderate_multiplier: f64,
+    },
+}
+
+impl StrategyConfig {
+    /// Constructs the configured scorer.
+    ///
+    /// Parameters go through the same validation as the scorers' own
+    /// constructors, since stored configs may have been edited by hand.
+    pub fn build(&self) -> Result<Box<dyn ScoringStrategy>, NemesisError> {
+        Ok(match self {
+            StrategyConfig::SimpleDifference => Box::new(SimpleDifferenceScorer),
+            StrategyConfig::EuclideanDistance => Box::new(EuclideanDistanceScorer),
+            StrategyConfig::CosineSimilarity => Box::new(CosineSimilarityScorer),
+            StrategyConfig::Weighted { weights } => Box::new(WeightedScorer::new(weights.clone())?),
+            StrategyConfig::WeightedCosine { weights, neutral } => {
+                Box::new(WeightedCosineScorer::new(weights.clone(), *neutral)?)
+            }
+            StrategyConfig::Polarization {
+                extreme_multiplier,
+                lean_multiplier,
+                moderate_multiplier,
+            } => Box::new(PolarizationScorer::new(
+                *extreme_multiplier,
+                *lean_multiplier,
+                *moderate_multiplier,
+            )?),
+        })
+    }
+}
+
+impl From<&PolarizationScorer> for StrategyConfig {
+    fn from(scorer: &PolarizationScorer) -> Self {
+        StrategyConfig::Polarization {
+            extreme_multiplier: scorer.extreme_multiplier,
+            lean_multiplier: scorer.lean_multiplier,
+            moderate_multiplier: scorer.moderate_multiplier,
+        }
+    }
+}
+
+impl From<&WeightedScorer> for StrategyConfig {
+    fn from(scorer: &WeightedScorer) -> Self {
+        StrategyConfig::Weighted {
+            weights: scorer.weights().to_vec(),
+        }
+    }
+}
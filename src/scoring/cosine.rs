@@ -0,0 +1,41 @@
+use crate::scoring::ScoringStrategy;
+use crate::user::{User, NEUTRAL_RESPONSE};
+
+/// Angle-based opposition: `1 - cosine_similarity` of the neutral-centred vectors.
+///
+/// Responses are centred on the neutral answer so that "agree" and "disagree"
+/// point in opposite directions. Scores range from 0 (same direction) to 2
+/// (exactly opposite). A user who answered neutral everywhere has no direction
+/// and scores 1 against everyone.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CosineSimilarityScorer;
+
+impl ScoringStrategy for CosineSimilarityScorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        let (mut dot, mut norm1, mut norm2) = (0.0, 0.0, 0.0);
+        for (&a, &b) in user1.responses.iter().zip(&user2.responses) {
+            let a = (a - NEUTRAL_RESPONSE) as f64;
+            let b = (b - NEUTRAL_RESPONSE) as f64;
+            dot += a * b;
+            norm1 += a * a;
+            norm2 += b * b;
+        }
+        if norm1 == 0.0 || norm2 == 0.0 {
+            return 1.0;
+        }
+        1.0 - dot / (norm1.sqrt() * norm2.sqrt())
+    }
+
+    fn name(&self) -> String {
+        "CosineSimilarity".to_string()
+    }
+
+    /// Angles don't grow with the number of questions; the score is already normalized.
+    fn normalized_per_question(&self, user1: &User, user2: &User) -> f64 {
+        self.calculate_score(user1, user2)
+    }
+
+    fn score_bounds(&self, _num_questions: usize) -> (f64, f64) {
+        (0.0, 2.0)
+    }
+}
@@ -0,0 +1,100 @@
+use crate::error::NemesisError;
+use crate::scoring::{
+    EuclideanDistanceScorer, PolarizationScorer, ScoringStrategy, SimpleDifferenceScorer,
+    WeightedScorer,
+};
+use crate::user::User;
+
+/// The built-in scorers as one enum, chosen at runtime but dispatched statically.
+///
+/// A `NemesisMatcher<Scorer>` avoids the per-call virtual dispatch of
+/// `Box<dyn ScoringStrategy>` on the O(n²) scoring loop; each call is a
+/// `match` the compiler can inline through.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Scorer {
+    Simple,
+    Euclidean,
+    Weighted(WeightedScorer),
+    Polarization(PolarizationScorer),
+}
+
+/// Runs `$body` with `$s` bound to the wrapped concrete scorer.
+macro_rules! dispatch {
+    ($self:expr, $s:ident => $body:expr) => {
+        match $self {
+            Scorer::Simple => {
+                let $s = &SimpleDifferenceScorer;
+                $body
+            }
+            Scorer::Euclidean => {
+                let $s = &EuclideanDistanceScorer;
+                $body
+            }
+            Scorer::Weighted($s) => $body,
+            Scorer::Polarization($s) => $body,
+        }
+    };
+}
+
+impl ScoringStrategy for Scorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        dispatch!(self, s => s.calculate_score(user1, user2))
+    }
+
+    fn try_calculate_score(&self, user1: &User, user2: &User) -> Result<f64, NemesisError> {
+        dispatch!(self, s => s.try_calculate_score(user1, user2))
+    }
+
+    fn name(&self) -> String {
+        dispatch!(self, s => s.name())
+    }
+
+    fn is_decomposable(&self) -> bool {
+        dispatch!(self, s => s.is_decomposable())
+    }
+
+    fn is_position_dependent(&self) -> bool {
+        dispatch!(self, s => s.is_position_dependent())
+    }
+
+    fn answer_sensitivity(
+        &self,
+        user1: &User,
+        user2: &User,
+        index: usize,
+    ) -> Result<f64, NemesisError> {
+        dispatch!(self, s => s.answer_sensitivity(user1, user2, index))
+    }
+
+    fn normalized_per_question(&self, user1: &User, user2: &User) -> f64 {
+        dispatch!(self, s => s.normalized_per_question(user1, user2))
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        dispatch!(self, s => s.score_bounds(num_questions))
+    }
+}
+
+impl From<SimpleDifferenceScorer> for Scorer {
+    fn from(_: SimpleDifferenceScorer) -> Self {
+        Scorer::Simple
+    }
+}
+
+impl From<EuclideanDistanceScorer> for Scorer {
+    fn from(_: EuclideanDistanceScorer) -> Self {
+        Scorer::Euclidean
+    }
+}
+
+impl From<WeightedScorer> for Scorer {
+    fn from(scorer: WeightedScorer) -> Self {
+        Scorer::Weighted(scorer)
+    }
+}
+
+impl From<PolarizationScorer> for Scorer {
+    fn from(scorer: PolarizationScorer) -> Self {
+        Scorer::Polarization(scorer)
+    }
+}
@@ -0,0 +1,42 @@
+use crate::scoring::ScoringStrategy;
+use crate::user::{User, MAX_RESPONSE, MIN_RESPONSE};
+
+/// Straight-line distance between the two response vectors.
+///
+/// Compared with [`SimpleDifferenceScorer`](crate::scoring::SimpleDifferenceScorer)
+/// this favours a few large disagreements over many small ones.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EuclideanDistanceScorer;
+
+impl ScoringStrategy for EuclideanDistanceScorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        // Squares are accumulated in f64: even i64 overflows for wide scales.
+        let sum_sq: f64 = user1
+            .responses
+            .iter()
+            .zip(&user2.responses)
+            .map(|(&a, &b)| {
+                let d = a as f64 - b as f64;
+                d * d
+            })
+            .sum();
+        sum_sq.sqrt()
+    }
+
+    fn name(&self) -> String {
+        "EuclideanDistance".to_string()
+    }
+
+    /// Distance grows with the square root of the question count, so divide by `√N`.
+    fn normalized_per_question(&self, user1: &User, user2: &User) -> f64 {
+        match user1.num_questions() {
+            0 => 0.0,
+            n => self.calculate_score(user1, user2) / (n as f64).sqrt(),
+        }
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        let span = (MAX_RESPONSE - MIN_RESPONSE) as f64;
+        (0.0, span * (num_questions as f64).sqrt())
+    }
+}
@@ -0,0 +1,86 @@
+use crate::error::NemesisError;
+use crate::scoring::WeightedScorer;
+use crate::user::User;
+
+/// Ridge term added to the normal equations so questions that never differ
+/// in the training data don't make the system singular.
+const RIDGE: f64 = 1e-9;
+
+/// Learns per-question weights from pairs labelled with the score they
+/// *should* have received (e.g. from post-event feedback).
+///
+/// Fits `target ≈ Σ_q w_q · |a_q - b_q|` by ordinary least squares, solving
+/// the normal equations directly. Weights the fit drives negative are clamped
+/// to zero, since [`WeightedScorer`] only accepts non-negative weights; when
+/// that happens the result is no longer the exact least-squares optimum.
+pub fn fit_weights(
+    pairs: &[(User, User, f64)],
+    num_questions: usize,
+) -> Result<WeightedScorer, NemesisError> {
+    if pairs.is_empty() || num_questions == 0 {
+        return Err(NemesisError::InvalidWeights(
+            "need at least one labelled pair and one question".to_string(),
+        ));
+    }
+    let n = num_questions;
+    let mut xtx = vec![vec![0.0; n]; n];
+    let mut xty = vec![0.0; n];
+    for (u1, u2, target) in pairs {
+        for user in [u1, u2] {
+            if user.num_questions() != n {
+                return Err(NemesisError::MismatchedLengths {
+                    expected: n,
+                    got: user.num_questions(),
+                });
+            }
+        }
+        let row: Vec<f64> = u1
+            .responses
+            .iter()
+            .zip(&u2.responses)
+            .map(|(&a, &b)| (a as f64 - b as f64).abs())
+            .collect();
+        for i in 0..n {
+            xty[i] += row[i] * target;
+            for j in 0..n {
+                xtx[i][j] += row[i] * row[j];
+            }
+        }
+    }
+    let trace: f64 = (0..n).map(|i| xtx[i][i]).sum();
+    for (i, row) in xtx.iter_mut().enumerate() {
+        row[i] += RIDGE * trace.max(1.0);
+    }
+
+    let weights = solve(xtx, xty)
+        .ok_or_else(|| NemesisError::InvalidWeights("training data is degenerate".to_string()))?;
+    WeightedScorer::new(weights.into_iter().map(|w| w.max(0.0)).collect())
+}
+
+/// Solves `a · x = b` by Gaussian elimination with partial pivoting.
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < f64::EPSILON {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let (upper, lower) = a.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let factor = row[col] / pivot_row[col];
+            for (cell, &p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *cell -= factor * p;
+            }
+            b[col + 1 + offset] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = ((row + 1)..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    x.iter().all(|v| v.is_finite()).then_some(x)
+}
@@ -0,0 +1,107 @@
+use crate::analysis::question_means;
+use crate::scoring::ScoringStrategy;
+use crate::user::{Scale, User};
+
+/// The answers a hypothetical perfect nemesis of `user` would give.
+///
+/// Starts from the scale endpoint furthest from each of the user's answers
+/// (the top of the scale on ties), which is already the exact optimum for
+/// per-question difference scorers such as
+/// [`SimpleDifferenceScorer`](crate::scoring::SimpleDifferenceScorer). For
+/// other scorers it then runs coordinate ascent: each question in turn is set
+/// to whichever answer on `scale` most raises the score with the others held
+/// fixed, repeating until no single change helps. That finds a local optimum,
+/// which for scorers like cosine can differ from the global one.
+pub fn ideal_opponent_responses<S: ScoringStrategy + ?Sized>(
+    user: &User,
+    scorer: &S,
+    scale: Scale,
+) -> Vec<i32> {
+    let start: Vec<i32> = user
+        .responses
+        .iter()
+        .map(|&r| scale.farthest_endpoint(r))
+        .collect();
+    let score =
+        |responses: &[i32]| scorer.calculate_score(user, &user.with_responses(responses.to_vec()));
+    coordinate_ascent(start, scale, score)
+}
+
+/// The answers that would oppose the whole pool the most, summed over
+/// everyone: a synthetic "average contrarian".
+///
+/// Starts from the scale endpoint furthest from each question's pool mean
+/// (the top on ties), which is the exact optimum for
+/// [`SimpleDifferenceScorer`](crate::scoring::SimpleDifferenceScorer) when
+/// the pool leans clearly to one side, then improves it by the same
+/// coordinate ascent as [`ideal_opponent_responses`]. Covers the longest
+/// answer sheet in the pool; an empty pool gives an empty profile.
+pub fn pool_nemesis_profile<S: ScoringStrategy + ?Sized>(
+    users: &[User],
+    scorer: &S,
+    scale: Scale,
+) -> Vec<i32> {
+    let start: Vec<i32> = question_means(users)
+        .into_iter()
+        .map(|mean| {
+            if mean - scale.min() as f64 > scale.max() as f64 - mean {
+                scale.min()
+            } else {
+                scale.max()
+            }
+        })
+        .collect();
+    let score = |responses: &[i32]| {
+        let nemesis = User::unchecked(String::new(), responses.to_vec());
+        users
+            .iter()
+            .map(|u| scorer.calculate_score(u, &nemesis))
+            .sum()
+    };
+    coordinate_ascent(start, scale, score)
+}
+
+/// The score between `user` and a copy of them who changed their mind on
+/// everything, each answer [mirrored](Scale::mirror) on `scale`.
+///
+/// A "what if they flipped entirely" figure: strongly opinionated users
+/// score high, while someone answering the midpoint throughout scores 0.
+pub fn self_mirror_opposition<S: ScoringStrategy + ?Sized>(
+    user: &User,
+    scorer: &S,
+    scale: Scale,
+) -> f64 {
+    let mirrored = user.responses.iter().map(|&r| scale.mirror(r)).collect();
+    scorer.calculate_score(user, &user.with_responses(mirrored))
+}
+
+/// Repeatedly sets each question to whichever answer on `scale` most raises
+/// `score`, until no single change helps.
+fn coordinate_ascent(mut best: Vec<i32>, scale: Scale, score: impl Fn(&[i32]) -> f64) -> Vec<i32> {
+    let mut best_score = score(&best);
+
+    // Each pass strictly improves the score, so this terminates; the cap only
+    // guards against scorers whose output isn't a function of the answers.
+    for _ in 0..best.len().max(1) * scale.values().count() {
+        let mut improved = false;
+        for q in 0..best.len() {
+            for answer in scale.values() {
+                if answer == best[q] {
+                    continue;
+                }
+                let mut candidate = best.clone();
+                candidate[q] = answer;
+                let candidate_score = score(&candidate);
+                if candidate_score > best_score {
+                    best = candidate;
+                    best_score = candidate_score;
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+    best
+}
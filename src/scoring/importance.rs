@@ -0,0 +1,64 @@
+use crate::error::NemesisError;
+use crate::scoring::ScoringStrategy;
+use crate::user::{User, MAX_RESPONSE, MIN_RESPONSE};
+
+/// Absolute difference per question, boosted where either user starred it.
+///
+/// A question contributes `|a - b| * multiplier` when at least one of the pair
+/// marked it [important](User::is_important), and the plain `|a - b|` (base
+/// weight 1) when neither did. Users without stars score exactly as under
+/// [`SimpleDifferenceScorer`](crate::scoring::SimpleDifferenceScorer).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImportanceScorer {
+    pub multiplier: f64,
+}
+
+impl Default for ImportanceScorer {
+    fn default() -> Self {
+        ImportanceScorer { multiplier: 2.0 }
+    }
+}
+
+impl ImportanceScorer {
+    /// Creates a scorer with a custom multiplier, which must be finite and non-negative.
+    pub fn new(multiplier: f64) -> Result<Self, NemesisError> {
+        if !multiplier.is_finite() || multiplier < 0.0 {
+            return Err(NemesisError::InvalidWeights(format!(
+                "importance multiplier {multiplier} must be finite and non-negative"
+            )));
+        }
+        Ok(ImportanceScorer { multiplier })
+    }
+}
+
+impl ScoringStrategy for ImportanceScorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        user1
+            .responses
+            .iter()
+            .zip(&user2.responses)
+            .enumerate()
+            .map(|(q, (&a, &b))| {
+                let weight = if user1.is_important(q) || user2.is_important(q) {
+                    self.multiplier
+                } else {
+                    1.0
+                };
+                (a as f64 - b as f64).abs() * weight
+            })
+            .sum()
+    }
+
+    fn name(&self) -> String {
+        "Importance".to_string()
+    }
+
+    fn is_decomposable(&self) -> bool {
+        true
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        let span = (MAX_RESPONSE - MIN_RESPONSE) as f64;
+        (0.0, span * self.multiplier.max(1.0) * num_questions as f64)
+    }
+}
@@ -0,0 +1,39 @@
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// Turns a low-is-opposite metric into a high-is-opposite one: `max - inner`.
+///
+/// Use it to drive the matcher with an existing metric whose most opposed
+/// pairs score *lowest*, without rewriting it. `max` should be the inner
+/// scorer's largest possible output (typically `inner.score_bounds(n).1`) so
+/// inverted scores stay non-negative.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InvertScorer<S> {
+    pub inner: S,
+    pub max: f64,
+}
+
+impl<S: ScoringStrategy> InvertScorer<S> {
+    pub fn new(inner: S, max: f64) -> Self {
+        InvertScorer { inner, max }
+    }
+}
+
+impl<S: ScoringStrategy> ScoringStrategy for InvertScorer<S> {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        self.max - self.inner.calculate_score(user1, user2)
+    }
+
+    fn name(&self) -> String {
+        format!("Invert({})", self.inner.name())
+    }
+
+    fn is_position_dependent(&self) -> bool {
+        self.inner.is_position_dependent()
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        let (low, high) = self.inner.score_bounds(num_questions);
+        (self.max - high, self.max - low)
+    }
+}
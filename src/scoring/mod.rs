@@ -0,0 +1,232 @@
+//! Opposition scoring strategies.
+//!
+//! Every strategy maps a pair of users to a non-negative score where *higher
+//! means more opposed*. The matcher is generic over [`ScoringStrategy`], so
+//! any of the scorers below (or a custom one) can drive it.
+
+mod breakdown;
+mod cached;
+mod cap;
+mod circular;
+mod clamp;
+mod confidence;
+mod config;
+mod cosine;
+mod enum_scorer;
+mod euclidean;
+mod fit;
+mod ideal;
+mod importance;
+mod invert;
+mod mutual_neutral;
+mod polarization;
+mod recording;
+mod shared_conviction;
+mod simple;
+mod table_conviction;
+mod weighted;
+mod weighted_cosine;
+mod zscore;
+
+pub use breakdown::{marginal_contribution, score_breakdown};
+pub use cached::CachedScorer;
+pub use cap::CapPerQuestion;
+pub use circular::CircularDifferenceScorer;
+pub use clamp::ClampScorer;
+pub use confidence::{ConfidenceCombine, ConfidenceScorer};
+pub use config::StrategyConfig;
+pub use cosine::CosineSimilarityScorer;
+pub use enum_scorer::Scorer;
+pub use euclidean::EuclideanDistanceScorer;
+pub use fit::fit_weights;
+pub use ideal::{ideal_opponent_responses, pool_nemesis_profile, self_mirror_opposition};
+pub use importance::ImportanceScorer;
+pub use invert::InvertScorer;
+pub use mutual_neutral::IgnoreMutualNeutral;
+pub use polarization::PolarizationScorer;
+pub use recording::RecordingScorer;
+pub use shared_conviction::SharedConvictionScorer;
+pub use simple::SimpleDifferenceScorer;
+pub use table_conviction::TableConvictionScorer;
+pub use weighted::WeightedScorer;
+pub use weighted_cosine::WeightedCosineScorer;
+pub use zscore::ZScoreScorer;
+
+use crate::error::NemesisError;
+use crate::user::User;
+
+/// Passes `score` through, or reports it as [`NemesisError::NonFiniteScore`]
+/// for the pair if it is NaN or infinite.
+pub(crate) fn check_finite(user1: &User, user2: &User, score: f64) -> Result<f64, NemesisError> {
+    if score.is_finite() {
+        Ok(score)
+    } else {
+        Err(NemesisError::NonFiniteScore {
+            user1_id: user1.id.clone(),
+            user2_id: user2.id.clone(),
+            score,
+        })
+    }
+}
+
+/// A way of measuring how opposed two users are.
+///
+/// Implementations assume both users answered the same number of questions;
+/// validate pools up front rather than relying on scorers to catch it.
+pub trait ScoringStrategy {
+    /// Opposition score for the pair. Higher means more opposed.
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64;
+
+    /// [`calculate_score`](Self::calculate_score), but rejecting NaN and
+    /// infinite results with [`NemesisError::NonFiniteScore`].
+    ///
+    /// A non-finite score compares equal to everything during greedy
+    /// selection and silently scrambles the order, so use this when the
+    /// scorer's configuration isn't trusted.
+    fn try_calculate_score(&self, user1: &User, user2: &User) -> Result<f64, NemesisError> {
+        check_finite(user1, user2, self.calculate_score(user1, user2))
+    }
+
+    /// Human-readable strategy name, used in logs and reports.
+    fn name(&self) -> String;
+
+    /// How much the pair's score changes if `user1`'s answer at `index`
+    /// goes up by one step, for "what if I changed this answer" previews.
+    ///
+    /// Defaults to rescoring with the bumped answer; strategies with a closed
+    /// form override it. An `index` past `user1`'s answers gives 0. The bumped
+    /// answer may leave the 1-7 scale, which scorers tolerate; an answer
+    /// already at `i32::MAX` stays put. Errors with
+    /// [`NemesisError::NonFiniteScore`] if either score isn't finite.
+    fn answer_sensitivity(
+        &self,
+        user1: &User,
+        user2: &User,
+        index: usize,
+    ) -> Result<f64, NemesisError> {
+        let Some(&answer) = user1.responses.get(index) else {
+            return Ok(0.0);
+        };
+        let mut bumped = user1.responses.clone();
+        bumped[index] = answer.saturating_add(1);
+        Ok(
+            self.try_calculate_score(&user1.with_responses(bumped), user2)?
+                - self.try_calculate_score(user1, user2)?,
+        )
+    }
+
+    /// Whether the score is a plain sum of independent per-question terms.
+    ///
+    /// Only then do per-question breakdowns such as
+    /// [`score_breakdown`] add up to the score. Defaults to `false`; Euclidean
+    /// and cosine scores, for example, are not decomposable.
+    fn is_decomposable(&self) -> bool {
+        false
+    }
+
+    /// Whether a question's position changes how it is scored, as with
+    /// per-question weights. Defaults to `false`; wrappers that drop
+    /// questions, such as [`IgnoreMutualNeutral`], reject such scorers.
+    fn is_position_dependent(&self) -> bool {
+        false
+    }
+
+    /// The pair's score expressed per question, so surveys of different
+    /// lengths can be compared.
+    ///
+    /// Defaults to dividing by the number of questions, which suits scores
+    /// that are sums of per-question terms. Strategies that grow differently
+    /// with length override it.
+    fn normalized_per_question(&self, user1: &User, user2: &User) -> f64 {
+        match user1.num_questions() {
+            0 => 0.0,
+            n => self.calculate_score(user1, user2) / n as f64,
+        }
+    }
+
+    /// Theoretical `(min, max)` score for users with `num_questions` answers.
+    ///
+    /// Defaults to `(0.0, f64::INFINITY)` for strategies without a known bound.
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        let _ = num_questions;
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl<T: ScoringStrategy + ?Sized> ScoringStrategy for &T {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        (**self).calculate_score(user1, user2)
+    }
+
+    fn try_calculate_score(&self, user1: &User, user2: &User) -> Result<f64, NemesisError> {
+        (**self).try_calculate_score(user1, user2)
+    }
+
+    fn name(&self) -> String {
+        (**self).name()
+    }
+
+    fn is_decomposable(&self) -> bool {
+        (**self).is_decomposable()
+    }
+
+    fn is_position_dependent(&self) -> bool {
+        (**self).is_position_dependent()
+    }
+
+    fn answer_sensitivity(
+        &self,
+        user1: &User,
+        user2: &User,
+        index: usize,
+    ) -> Result<f64, NemesisError> {
+        (**self).answer_sensitivity(user1, user2, index)
+    }
+
+    fn normalized_per_question(&self, user1: &User, user2: &User) -> f64 {
+        (**self).normalized_per_question(user1, user2)
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        (**self).score_bounds(num_questions)
+    }
+}
+
+impl<T: ScoringStrategy + ?Sized> ScoringStrategy for Box<T> {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        (**self).calculate_score(user1, user2)
+    }
+
+    fn try_calculate_score(&self, user1: &User, user2: &User) -> Result<f64, NemesisError> {
+        (**self).try_calculate_score(user1, user2)
+    }
+
+    fn name(&self) -> String {
+        (**self).name()
+    }
+
+    fn is_decomposable(&self) -> bool {
+        (**self).is_decomposable()
+    }
+
+    fn is_position_dependent(&self) -> bool {
+        (**self).is_position_dependent()
+    }
+
+    fn answer_sensitivity(
+        &self,
+        user1: &User,
+        user2: &User,
+        index: usize,
+    ) -> Result<f64, NemesisError> {
+        (**self).answer_sensitivity(user1, user2, index)
+    }
+
+    fn normalized_per_question(&self, user1: &User, user2: &User) -> f64 {
+        (**self).normalized_per_question(user1, user2)
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        (**self).score_bounds(num_questions)
+    }
+}
@@ -0,0 +1,87 @@
+use crate::error::NemesisError;
+use crate::scoring::ScoringStrategy;
+use crate::user::{User, NEUTRAL_RESPONSE};
+
+/// Drops questions both users answered neutrally before delegating to `inner`.
+///
+/// Mutual indifference carries no opposition signal, but a scorer that
+/// averages over questions would still count it in the denominator. The inner
+/// scorer only ever sees the remaining questions, with confidences and stars
+/// kept alongside them. A pair that is neutral on everything scores 0.
+#[derive(Debug, Clone, Copy)]
+pub struct IgnoreMutualNeutral<S> {
+    inner: S,
+}
+
+impl<S: ScoringStrategy> IgnoreMutualNeutral<S> {
+    /// Errors with [`NemesisError::PositionDependent`] if `inner`
+    /// [depends on question positions](ScoringStrategy::is_position_dependent),
+    /// since dropping questions would shift, say, a
+    /// [`WeightedScorer`](crate::scoring::WeightedScorer)'s weights.
+    pub fn new(inner: S) -> Result<Self, NemesisError> {
+        if inner.is_position_dependent() {
+            return Err(NemesisError::PositionDependent(inner.name()));
+        }
+        Ok(IgnoreMutualNeutral { inner })
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Both users restricted to the questions that aren't mutually neutral,
+    /// or `None` if there are no such questions.
+    fn reduce(user1: &User, user2: &User) -> Option<(User, User)> {
+        let kept: Vec<usize> = user1
+            .responses
+            .iter()
+            .zip(&user2.responses)
+            .enumerate()
+            .filter(|(_, (&a, &b))| !(a == NEUTRAL_RESPONSE && b == NEUTRAL_RESPONSE))
+            .map(|(q, _)| q)
+            .collect();
+        if kept.is_empty() {
+            return None;
+        }
+        Some((keep_questions(user1, &kept), keep_questions(user2, &kept)))
+    }
+}
+
+impl<S: ScoringStrategy> ScoringStrategy for IgnoreMutualNeutral<S> {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        Self::reduce(user1, user2).map_or(0.0, |(u1, u2)| self.inner.calculate_score(&u1, &u2))
+    }
+
+    fn normalized_per_question(&self, user1: &User, user2: &User) -> f64 {
+        Self::reduce(user1, user2)
+            .map_or(0.0, |(u1, u2)| self.inner.normalized_per_question(&u1, &u2))
+    }
+
+    fn name(&self) -> String {
+        format!("IgnoreMutualNeutral({})", self.inner.name())
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        self.inner.score_bounds(num_questions)
+    }
+}
+
+/// `user` restricted to the questions in `kept`, with any confidences and
+/// stars following their answers.
+fn keep_questions(user: &User, kept: &[usize]) -> User {
+    let n = user.responses.len();
+    User {
+        confidence: pick(&user.confidence, n, kept),
+        important: pick(&user.important, n, kept),
+        ..user.with_responses(pick(&user.responses, n, kept))
+    }
+}
+
+/// The entries of `values` at `kept`, or nothing if `values` doesn't have
+/// one entry per question.
+fn pick<T: Copy>(values: &[T], num_questions: usize, kept: &[usize]) -> Vec<T> {
+    if values.len() != num_questions {
+        return Vec::new();
+    }
+    kept.iter().map(|&q| values[q]).collect()
+}
@@ -0,0 +1,89 @@
+use crate::error::NemesisError;
+use crate::scoring::ScoringStrategy;
+use crate::user::{User, MAX_RESPONSE, MIN_RESPONSE, NEUTRAL_RESPONSE};
+
+/// Rewards disagreements between people who both hold their views strongly.
+///
+/// Each answer is placed in a conviction band by its distance from neutral:
+/// extreme (1 or 7), lean (2 or 6) or moderate (3, 4 or 5). A question's
+/// contribution is `|a - b| * conviction(a) * conviction(b)`, so a 1-vs-7 clash
+/// counts far more than a 3-vs-5 one even though both sit either side of neutral.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolarizationScorer {
+    pub extreme_multiplier: f64,
+    pub lean_multiplier: f64,
+    pub moderate_multiplier: f64,
+}
+
+impl Default for PolarizationScorer {
+    fn default() -> Self {
+        PolarizationScorer {
+            extreme_multiplier: 2.0,
+            lean_multiplier: 1.5,
+            moderate_multiplier: 1.0,
+        }
+    }
+}
+
+impl PolarizationScorer {
+    /// Creates a scorer with custom band multipliers, which must be finite and non-negative.
+    pub fn new(
+        extreme_multiplier: f64,
+        lean_multiplier: f64,
+        moderate_multiplier: f64,
+    ) -> Result<Self, NemesisError> {
+        for (band, m) in [
+            ("extreme", extreme_multiplier),
+            ("lean", lean_multiplier),
+            ("moderate", moderate_multiplier),
+        ] {
+            if !m.is_finite() || m < 0.0 {
+                return Err(NemesisError::InvalidWeights(format!(
+                    "{band} multiplier {m} must be finite and non-negative"
+                )));
+            }
+        }
+        Ok(PolarizationScorer {
+            extreme_multiplier,
+            lean_multiplier,
+            moderate_multiplier,
+        })
+    }
+
+    /// Conviction multiplier for a single answer.
+    pub fn conviction(&self, response: i32) -> f64 {
+        match (response as i64 - NEUTRAL_RESPONSE as i64).abs() {
+            3 => self.extreme_multiplier,
+            2 => self.lean_multiplier,
+            _ => self.moderate_multiplier,
+        }
+    }
+}
+
+impl ScoringStrategy for PolarizationScorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        user1
+            .responses
+            .iter()
+            .zip(&user2.responses)
+            .map(|(&a, &b)| (a as f64 - b as f64).abs() * self.conviction(a) * self.conviction(b))
+            .sum()
+    }
+
+    fn name(&self) -> String {
+        "Polarization".to_string()
+    }
+
+    fn is_decomposable(&self) -> bool {
+        true
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        let span = (MAX_RESPONSE - MIN_RESPONSE) as f64;
+        let top = self
+            .extreme_multiplier
+            .max(self.lean_multiplier)
+            .max(self.moderate_multiplier);
+        (0.0, span * top * top * num_questions as f64)
+    }
+}
@@ -0,0 +1,86 @@
+use std::cell::{Ref, RefCell};
+
+use crate::error::NemesisError;
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// Delegates to `inner` and keeps a log of every score it computes.
+///
+/// Meant for debugging strategy behaviour: the log grows without bound and
+/// the `RefCell` makes the wrapper unusable across threads, so don't leave it
+/// in production paths. Only [`calculate_score`](ScoringStrategy::calculate_score)
+/// calls and successful [`try_calculate_score`](ScoringStrategy::try_calculate_score)
+/// calls are recorded; the other methods go straight to `inner`.
+#[derive(Debug, Clone, Default)]
+pub struct RecordingScorer<S> {
+    pub inner: S,
+    records: RefCell<Vec<(String, String, f64)>>,
+}
+
+impl<S: ScoringStrategy> RecordingScorer<S> {
+    pub fn new(inner: S) -> Self {
+        RecordingScorer {
+            inner,
+            records: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Every `(user1_id, user2_id, score)` computed so far, oldest first.
+    ///
+    /// The returned guard borrows the log; drop it before scoring again.
+    pub fn records(&self) -> Ref<'_, [(String, String, f64)]> {
+        Ref::map(self.records.borrow(), Vec::as_slice)
+    }
+
+    /// Empties the log, returning what it held.
+    pub fn take_records(&self) -> Vec<(String, String, f64)> {
+        self.records.take()
+    }
+}
+
+impl<S: ScoringStrategy> ScoringStrategy for RecordingScorer<S> {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        let score = self.inner.calculate_score(user1, user2);
+        self.records
+            .borrow_mut()
+            .push((user1.id.clone(), user2.id.clone(), score));
+        score
+    }
+
+    fn try_calculate_score(&self, user1: &User, user2: &User) -> Result<f64, NemesisError> {
+        let score = self.inner.try_calculate_score(user1, user2)?;
+        self.records
+            .borrow_mut()
+            .push((user1.id.clone(), user2.id.clone(), score));
+        Ok(score)
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn is_decomposable(&self) -> bool {
+        self.inner.is_decomposable()
+    }
+
+    fn is_position_dependent(&self) -> bool {
+        self.inner.is_position_dependent()
+    }
+
+    fn answer_sensitivity(
+        &self,
+        user1: &User,
+        user2: &User,
+        index: usize,
+    ) -> Result<f64, NemesisError> {
+        self.inner.answer_sensitivity(user1, user2, index)
+    }
+
+    fn normalized_per_question(&self, user1: &User, user2: &User) -> f64 {
+        self.inner.normalized_per_question(user1, user2)
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        self.inner.score_bounds(num_questions)
+    }
+}
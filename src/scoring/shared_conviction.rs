@@ -0,0 +1,36 @@
+use crate::scoring::ScoringStrategy;
+use crate::user::{User, MAX_RESPONSE, MIN_RESPONSE};
+
+/// Counts the questions where both users sit at the same extreme.
+///
+/// The inverse use-case of the nemesis matcher: for coalition-building,
+/// "most opposed" becomes "most strongly aligned". Each question where both
+/// answered 1, or both answered 7, adds 1; anything else, including shared
+/// moderate answers and opposite extremes, adds nothing. Plug it into
+/// [`NemesisMatcher`](crate::NemesisMatcher) to pair up allies instead of
+/// opponents.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SharedConvictionScorer;
+
+impl ScoringStrategy for SharedConvictionScorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        user1
+            .responses
+            .iter()
+            .zip(&user2.responses)
+            .filter(|&(&a, &b)| a == b && (a == MIN_RESPONSE || a == MAX_RESPONSE))
+            .count() as f64
+    }
+
+    fn name(&self) -> String {
+        "SharedConviction".to_string()
+    }
+
+    fn is_decomposable(&self) -> bool {
+        true
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        (0.0, num_questions as f64)
+    }
+}
@@ -0,0 +1,52 @@
+use crate::error::NemesisError;
+use crate::scoring::ScoringStrategy;
+use crate::user::{User, MAX_RESPONSE, MIN_RESPONSE};
+
+/// Sums the absolute difference between the two users' answers.
+///
+/// A pair answering 1 and 7 on every question scores `6 * num_questions`.
+/// Differences are accumulated as `i64`, so even out-of-scale values on very
+/// long questionnaires can't overflow.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SimpleDifferenceScorer;
+
+impl ScoringStrategy for SimpleDifferenceScorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        user1
+            .responses
+            .iter()
+            .zip(&user2.responses)
+            .map(|(&a, &b)| (a as i64 - b as i64).abs())
+            .sum::<i64>() as f64
+    }
+
+    fn name(&self) -> String {
+        "SimpleDifference".to_string()
+    }
+
+    fn is_decomposable(&self) -> bool {
+        true
+    }
+
+    /// +1 when `user1`'s answer is at or above `user2`'s (moving it up widens
+    /// the gap), -1 when below.
+    fn answer_sensitivity(
+        &self,
+        user1: &User,
+        user2: &User,
+        index: usize,
+    ) -> Result<f64, NemesisError> {
+        match (user1.responses.get(index), user2.responses.get(index)) {
+            (Some(a), Some(b)) if a >= b => Ok(1.0),
+            (Some(_), Some(_)) => Ok(-1.0),
+            _ => Ok(0.0),
+        }
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        (
+            0.0,
+            ((MAX_RESPONSE - MIN_RESPONSE) as usize * num_questions) as f64,
+        )
+    }
+}
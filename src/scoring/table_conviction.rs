@@ -0,0 +1,67 @@
+use crate::error::NemesisError;
+use crate::scoring::ScoringStrategy;
+use crate::user::{User, MAX_RESPONSE, MIN_RESPONSE};
+
+/// [`PolarizationScorer`](crate::PolarizationScorer) with a conviction weight
+/// for every answer value instead of three bands.
+///
+/// `table[v - 1]` is the weight of answer `v`, and a question contributes
+/// `|a - b| * weight(a) * weight(b)`. The table
+/// `[2.0, 1.5, 1.0, 1.0, 1.0, 1.5, 2.0]` reproduces the default
+/// polarization bands; asymmetric tables can, say, count strong agreement
+/// more than strong disagreement. Answers off the 1-7 scale weigh 0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableConvictionScorer {
+    pub table: [f64; 7],
+}
+
+impl TableConvictionScorer {
+    /// Creates a scorer from a weight table, whose entries must be finite and non-negative.
+    pub fn new(table: [f64; 7]) -> Result<Self, NemesisError> {
+        if let Some((index, w)) = table
+            .iter()
+            .enumerate()
+            .find(|(_, w)| !w.is_finite() || **w < 0.0)
+        {
+            return Err(NemesisError::InvalidWeights(format!(
+                "weight {w} for answer {} must be finite and non-negative",
+                index + 1
+            )));
+        }
+        Ok(TableConvictionScorer { table })
+    }
+
+    /// Conviction weight for a single answer.
+    pub fn conviction(&self, response: i32) -> f64 {
+        if (MIN_RESPONSE..=MAX_RESPONSE).contains(&response) {
+            self.table[(response - MIN_RESPONSE) as usize]
+        } else {
+            0.0
+        }
+    }
+}
+
+impl ScoringStrategy for TableConvictionScorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        user1
+            .responses
+            .iter()
+            .zip(&user2.responses)
+            .map(|(&a, &b)| (a as f64 - b as f64).abs() * self.conviction(a) * self.conviction(b))
+            .sum()
+    }
+
+    fn name(&self) -> String {
+        "TableConviction".to_string()
+    }
+
+    fn is_decomposable(&self) -> bool {
+        true
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        let span = (MAX_RESPONSE - MIN_RESPONSE) as f64;
+        let top = self.table.iter().copied().fold(0.0, f64::max);
+        (0.0, span * top * top * num_questions as f64)
+    }
+}
@@ -0,0 +1,218 @@
+use crate::error::NemesisError;
+use crate::scoring::{check_finite, ScoringStrategy};
+use crate::user::{PartialUser, User, MAX_RESPONSE, MIN_RESPONSE};
+
+/// Absolute difference per question, scaled by a per-question importance weight.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedScorer {
+    weights: Vec<f64>,
+}
+
+impl WeightedScorer {
+    /// Creates a scorer with one weight per question.
+    ///
+    /// Weights must be finite and non-negative, and the vector must not be empty.
+    pub fn new(weights: Vec<f64>) -> Result<Self, NemesisError> {
+        validate_weights(&weights)?;
+        Ok(WeightedScorer { weights })
+    }
+
+    /// Creates a scorer from a few per-question weights, every other question
+    /// getting `default`.
+    ///
+    /// Handy for long surveys where only a handful of questions matter: with
+    /// a `default` of 0 the rest are ignored entirely. Errors if an override
+    /// index is not below `num_questions`, and applies the same weight checks
+    /// as [`new`](Self::new) to the resulting vector.
+    pub fn from_sparse(
+        num_questions: usize,
+        overrides: &[(usize, f64)],
+        default: f64,
+    ) -> Result<Self, NemesisError> {
+        let mut weights = vec![default; num_questions];
+        for &(index, weight) in overrides {
+            let slot = weights.get_mut(index).ok_or_else(|| {
+                NemesisError::InvalidWeights(format!(
+                    "override index {index} is out of range for {num_questions} questions"
+                ))
+            })?;
+            *slot = weight;
+        }
+        Self::new(weights)
+    }
+
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Weights that give every question equal influence on `users`.
+    ///
+    /// Each question is weighted by the inverse of its mean absolute answer
+    /// gap over all pairs in the pool, so on average every question adds 1 to
+    /// a pair's score no matter how polarized the pool is on it. A question
+    /// with no spread (everyone answered alike) gets weight 0 rather than an
+    /// infinite one. An empty pool yields a scorer with no weights.
+    pub fn balanced_for(users: &[User]) -> WeightedScorer {
+        let num_questions = users.iter().map(User::num_questions).max().unwrap_or(0);
+        let weights = (0..num_questions)
+            .map(|q| {
+                let answers: Vec<i64> = users
+                    .iter()
+                    .filter_map(|u| u.responses.get(q))
+                    .map(|&r| r as i64)
+                    .collect();
+                let mut total = 0i64;
+                let mut pairs = 0usize;
+                for (i, &a) in answers.iter().enumerate() {
+                    for &b in &answers[i + 1..] {
+                        total += (a - b).abs();
+                        pairs += 1;
+                    }
+                }
+                if total == 0 {
+                    0.0
+                } else {
+                    pairs as f64 / total as f64
+                }
+            })
+            .collect();
+        WeightedScorer { weights }
+    }
+
+    /// Weights each question by the fraction of `pool` who answered it.
+    ///
+    /// A question most people skipped carries little signal even after
+    /// [`impute_missing`](crate::user::impute_missing) fills it in, so it
+    /// counts for correspondingly little: a question answered by a quarter of
+    /// the pool has weight 0.25. Rates are computed once here and stored; score
+    /// the imputed users with the result. An empty pool yields a scorer with
+    /// no weights.
+    pub fn by_response_rate(pool: &[PartialUser]) -> WeightedScorer {
+        let num_questions = pool.iter().map(|u| u.responses.len()).max().unwrap_or(0);
+        let weights = (0..num_questions)
+            .map(|q| {
+                let answered = pool
+                    .iter()
+                    .filter(|u| u.responses.get(q).is_some_and(Option::is_some))
+                    .count();
+                answered as f64 / pool.len() as f64
+            })
+            .collect();
+        WeightedScorer { weights }
+    }
+}
+
+/// Whether `score` is above `max` by more than float rounding in the sum.
+fn exceeds(score: f64, max: f64) -> bool {
+    score > max + max.abs() * 1e-9
+}
+
+fn on_scale(user: &User) -> bool {
+    user.responses
+        .iter()
+        .all(|r| (MIN_RESPONSE..=MAX_RESPONSE).contains(r))
+}
+
+/// Shared weight validation for every weight-vector based scorer.
+pub(crate) fn validate_weights(weights: &[f64]) -> Result<(), NemesisError> {
+    if weights.is_empty() {
+        return Err(NemesisError::InvalidWeights("no weights given".to_string()));
+    }
+    if let Some((index, w)) = weights
+        .iter()
+        .enumerate()
+        .find(|(_, w)| !w.is_finite() || **w < 0.0)
+    {
+        return Err(NemesisError::InvalidWeights(format!(
+            "weight {w} at index {index} must be finite and non-negative"
+        )));
+    }
+    Ok(())
+}
+
+impl WeightedScorer {
+    /// The [`score_bounds`](ScoringStrategy::score_bounds) maximum for this
+    /// pair's shared questions.
+    fn max_for(&self, user1: &User, user2: &User) -> f64 {
+        let shared = user1.num_questions().min(user2.num_questions());
+        self.score_bounds(shared).1
+    }
+}
+
+impl ScoringStrategy for WeightedScorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        let score = user1
+            .responses
+            .iter()
+            .zip(&user2.responses)
+            .zip(&self.weights)
+            .map(|((&a, &b), w)| (a as f64 - b as f64).abs() * w)
+            .sum();
+        debug_assert!(
+            !(on_scale(user1) && on_scale(user2) && exceeds(score, self.max_for(user1, user2))),
+            "weighted score {score} exceeds its theoretical maximum"
+        );
+        score
+    }
+
+    /// Also rejects scores above the theoretical maximum with
+    /// [`NemesisError::ScoreOutOfBounds`], which catches answers off the
+    /// 1-7 scale slipping in through unvalidated deserialization.
+    fn try_calculate_score(&self, user1: &User, user2: &User) -> Result<f64, NemesisError> {
+        let score = check_finite(user1, user2, self.calculate_score(user1, user2))?;
+        let max = self.max_for(user1, user2);
+        if exceeds(score, max) {
+            return Err(NemesisError::ScoreOutOfBounds {
+                user1_id: user1.id.clone(),
+                user2_id: user2.id.clone(),
+                score,
+                max,
+            });
+        }
+        Ok(score)
+    }
+
+    fn name(&self) -> String {
+        "Weighted".to_string()
+    }
+
+    fn is_position_dependent(&self) -> bool {
+        true
+    }
+
+    fn is_decomposable(&self) -> bool {
+        true
+    }
+
+    /// The question's weight, signed as for
+    /// [`SimpleDifferenceScorer`](crate::scoring::SimpleDifferenceScorer).
+    fn answer_sensitivity(
+        &self,
+        user1: &User,
+        user2: &User,
+        index: usize,
+    ) -> Result<f64, NemesisError> {
+        let weight = self.weights.get(index).copied().unwrap_or(0.0);
+        match (user1.responses.get(index), user2.responses.get(index)) {
+            (Some(a), Some(b)) if a >= b => Ok(weight),
+            (Some(_), Some(_)) => Ok(-weight),
+            _ => Ok(0.0),
+        }
+    }
+
+    /// Divides by the total weight of the answered questions, giving a
+    /// weighted mean difference.
+    fn normalized_per_question(&self, user1: &User, user2: &User) -> f64 {
+        let total: f64 = self.weights.iter().take(user1.num_questions()).sum();
+        if total == 0.0 {
+            return 0.0;
+        }
+        self.calculate_score(user1, user2) / total
+    }
+
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        let span = (MAX_RESPONSE - MIN_RESPONSE) as f64;
+        let total: f64 = self.weights.iter().take(num_questions).sum();
+        (0.0, span * total)
+    }
+}
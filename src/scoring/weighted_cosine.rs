@@ -0,0 +1,78 @@
+use crate::error::NemesisError;
+use crate::scoring::weighted::validate_weights;
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// Cosine opposition with per-question weights and a configurable centre.
+///
+/// Each answer is centred by subtracting `neutral` and then scaled by its
+/// question's weight before taking `1 - cosine_similarity`. A heavily weighted
+/// question therefore dominates the angle between the two vectors. As with
+/// [`CosineSimilarityScorer`](crate::scoring::CosineSimilarityScorer), scores
+/// range from 0 to 2 and a user with no direction scores 1 against everyone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedCosineScorer {
+    weights: Vec<f64>,
+    neutral: f64,
+}
+
+impl WeightedCosineScorer {
+    /// Creates the scorer, validating weights like
+    /// [`WeightedScorer::new`](crate::scoring::WeightedScorer::new) and requiring a finite centre.
+    pub fn new(weights: Vec<f64>, neutral: f64) -> Result<Self, NemesisError> {
+        validate_weights(&weights)?;
+        if !neutral.is_finite() {
+            return Err(NemesisError::InvalidWeights(format!(
+                "neutral point {neutral} must be finite"
+            )));
+        }
+        Ok(WeightedCosineScorer { weights, neutral })
+    }
+
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    pub fn neutral(&self) -> f64 {
+        self.neutral
+    }
+}
+
+impl ScoringStrategy for WeightedCosineScorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        let (mut dot, mut norm1, mut norm2) = (0.0, 0.0, 0.0);
+        for ((&a, &b), &w) in user1
+            .responses
+            .iter()
+            .zip(&user2.responses)
+            .zip(&self.weights)
+        {
+            let a = (a as f64 - self.neutral) * w;
+            let b = (b as f64 - self.neutral) * w;
+            dot += a * b;
+            norm1 += a * a;
+            norm2 += b * b;
+        }
+        if norm1 == 0.0 || norm2 == 0.0 {
+            return 1.0;
+        }
+        1.0 - dot / (norm1.sqrt() * norm2.sqrt())
+    }
+
+    fn name(&self) -> String {
+        "WeightedCosine".to_string()
+    }
+
+    fn is_position_dependent(&self) -> bool {
+        true
+    }
+
+    /// Angles don't grow with the number of questions; the score is already normalized.
+    fn normalized_per_question(&self, user1: &User, user2: &User) -> f64 {
+        self.calculate_score(user1, user2)
+    }
+
+    fn score_bounds(&self, _num_questions: usize) -> (f64, f64) {
+        (0.0, 2.0)
+    }
+}
@@ -0,0 +1,68 @@
+use crate::scoring::ScoringStrategy;
+use crate::user::User;
+
+/// Euclidean distance between the users' standardized answers.
+///
+/// Each user's answers are z-scored against their own mean and (population)
+/// standard deviation first, so someone who answers 3-4-5 and someone who
+/// answers 1-4-7 hold the same relative opinions and score 0. This compares
+/// what people feel more or less strongly about rather than how much of the
+/// scale they use. A user who gives the same answer to everything has no
+/// relative opinions: their standardized answers are all 0, so they sit at
+/// distance `√N` from any user with some spread, and 0 from each other.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ZScoreScorer;
+
+impl ZScoreScorer {
+    /// `user`'s answers as z-scores, or all zeros if they don't vary.
+    pub fn standardize(user: &User) -> Vec<f64> {
+        let n = user.num_questions();
+        if n == 0 {
+            return Vec::new();
+        }
+        let mean = user.responses.iter().map(|&r| r as f64).sum::<f64>() / n as f64;
+        let variance = user
+            .responses
+            .iter()
+            .map(|&r| (r as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        if variance == 0.0 {
+            return vec![0.0; n];
+        }
+        let std_dev = variance.sqrt();
+        user.responses
+            .iter()
+            .map(|&r| (r as f64 - mean) / std_dev)
+            .collect()
+    }
+}
+
+impl ScoringStrategy for ZScoreScorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        Self::standardize(user1)
+            .iter()
+            .zip(&Self::standardize(user2))
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    fn name(&self) -> String {
+        "ZScore".to_string()
+    }
+
+    /// Distance grows with the square root of the question count, so divide by `√N`.
+    fn normalized_per_question(&self, user1: &User, user2: &User) -> f64 {
+        match user1.num_questions() {
+            0 => 0.0,
+            n => self.calculate_score(user1, user2) / (n as f64).sqrt(),
+        }
+    }
+
+    /// Standardized vectors have length `√N` (or 0), so two of them are at
+    /// most `2√N` apart.
+    fn score_bounds(&self, num_questions: usize) -> (f64, f64) {
+        (0.0, 2.0 * (num_questions as f64).sqrt())
+    }
+}
@@ -0,0 +1,25 @@
+//! Helpers for benchmarks and property tests. Enabled by the `test-util` feature.
+
+use crate::rng::SplitMix64;
+use crate::user::{User, MAX_RESPONSE, MIN_RESPONSE};
+
+/// Generates `count` valid users with uniformly random answers.
+///
+/// Ids are `user-0`, `user-1`, … and the same `seed` always yields the same
+/// pool, so benches and property tests are reproducible. Panics if
+/// `num_questions` is 0 and `count` isn't, as users need at least one answer.
+pub fn random_users(count: usize, num_questions: usize, seed: u64) -> Vec<User> {
+    assert!(
+        count == 0 || num_questions > 0,
+        "random users need at least one question"
+    );
+    let mut rng = SplitMix64::new(seed);
+    (0..count)
+        .map(|i| {
+            let responses = (0..num_questions)
+                .map(|_| rng.range_i32(MIN_RESPONSE, MAX_RESPONSE))
+                .collect();
+            User::new(format!("user-{i}"), responses).expect("generated users are valid")
+        })
+        .collect()
+}
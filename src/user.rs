@@ -0,0 +1,554 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::NemesisError;
+
+/// Lowest answer on the questionnaire scale ("strongly disagree").
+pub const MIN_RESPONSE: i32 = 1;
+/// Highest answer on the questionnaire scale ("strongly agree").
+pub const MAX_RESPONSE: i32 = 7;
+/// The neutral midpoint of the scale.
+pub const NEUTRAL_RESPONSE: i32 = 4;
+
+/// The range of answers a questionnaire accepts, inclusive at both ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scale {
+    min: i32,
+    max: i32,
+}
+
+impl Default for Scale {
+    /// The crate's 1-7 scale.
+    fn default() -> Self {
+        Scale {
+            min: MIN_RESPONSE,
+            max: MAX_RESPONSE,
+        }
+    }
+}
+
+impl Scale {
+    /// A scale from `min` to `max`; `min` must be below `max`.
+    pub fn new(min: i32, max: i32) -> Result<Self, NemesisError> {
+        if min >= max {
+            return Err(NemesisError::InvalidScale { min, max });
+        }
+        Ok(Scale { min, max })
+    }
+
+    pub fn min(&self) -> i32 {
+        self.min
+    }
+
+    pub fn max(&self) -> i32 {
+        self.max
+    }
+
+    /// Whether `answer` is on the scale.
+    pub fn contains(&self, answer: i32) -> bool {
+        (self.min..=self.max).contains(&answer)
+    }
+
+    /// Every answer on the scale, lowest first.
+    pub fn values(&self) -> std::ops::RangeInclusive<i32> {
+        self.min..=self.max
+    }
+
+    /// `answer` reflected about the middle of the scale, so the minimum and
+    /// maximum swap and the midpoint stays put. Answers off the scale may
+    /// reflect past the `i32` range; those saturate at its ends.
+    pub fn mirror(&self, answer: i32) -> i32 {
+        let mirrored = self.min as i64 + self.max as i64 - answer as i64;
+        mirrored.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    }
+
+    /// The endpoint furthest from `answer`; the top of the scale on ties.
+    pub fn farthest_endpoint(&self, answer: i32) -> i32 {
+        if (answer as i64 - self.min as i64).abs() > (self.max as i64 - answer as i64).abs() {
+            self.min
+        } else {
+            self.max
+        }
+    }
+}
+
+/// Infers a batch's answer scale from the lowest and highest answers seen.
+///
+/// This can't tell an endpoint nobody chose from a genuinely shorter scale:
+/// a 1-7 survey where no one answered 6 or 7 is reported as 1-5. Batches
+/// with fewer than two distinct answers fall back to the default 1-7 scale.
+pub fn detect_scale(users: &[User]) -> Scale {
+    let answers = users.iter().flat_map(|u| u.responses.iter().copied());
+    let (Some(min), Some(max)) = (answers.clone().min(), answers.max()) else {
+        return Scale::default();
+    };
+    Scale::new(min, max).unwrap_or_default()
+}
+
+/// Serialization schema version written by this build.
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// Schema version assumed for payloads written before versioning existed.
+fn legacy_schema_version() -> u32 {
+    1
+}
+
+/// A questionnaire participant and their answers on the 1-7 scale.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    #[serde(deserialize_with = "deserialize_id")]
+    pub id: String,
+    pub responses: Vec<i32>,
+    /// Layout version of the serialized form; payloads without it are version 1.
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
+    /// Free-form attributes such as region or cohort, used by tag constraints.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tags: HashMap<String, String>,
+    /// How sure the user was of each answer, from 0.0 (a guess) to 1.0
+    /// (certain). Empty means every answer is fully certain.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub confidence: Vec<f64>,
+    /// Questions the user starred as important to them. Empty means none.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub important: Vec<bool>,
+}
+
+impl User {
+    /// Creates a user, rejecting blank ids, empty response lists and
+    /// out-of-range answers.
+    ///
+    /// Surrounding whitespace is trimmed from `id`, so `" alice "` and
+    /// `"alice"` are the same user.
+    pub fn new(id: impl Into<String>, responses: Vec<i32>) -> Result<Self, NemesisError> {
+        let id = validate_id(id.into())?;
+        validate_responses(&responses)?;
+        Ok(User::unchecked(id, responses))
+    }
+
+    /// Builds a user whose responses are already known to be valid.
+    pub(crate) fn unchecked(id: String, responses: Vec<i32>) -> User {
+        User {
+            id,
+            responses,
+            schema_version: CURRENT_SCHEMA_VERSION,
+            tags: HashMap::new(),
+            confidence: Vec::new(),
+            important: Vec::new(),
+        }
+    }
+
+    /// Adds or replaces a tag.
+    pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.insert(key.into(), value.into());
+        self
+    }
+
+    /// The value of tag `key`, if set.
+    pub fn tag(&self, key: &str) -> Option<&str> {
+        self.tags.get(key).map(String::as_str)
+    }
+
+    /// Attaches a confidence for each answer.
+    ///
+    /// There must be one value per response, each finite and within
+    /// `0.0..=1.0`.
+    pub fn with_confidence(mut self, confidence: Vec<f64>) -> Result<Self, NemesisError> {
+        if confidence.len() != self.responses.len() {
+            return Err(NemesisError::MismatchedLengths {
+                expected: self.responses.len(),
+                got: confidence.len(),
+            });
+        }
+        if let Some((index, &value)) = confidence
+            .iter()
+            .enumerate()
+            .find(|(_, c)| !(0.0..=1.0).contains(*c))
+        {
+            return Err(NemesisError::InvalidConfidence { index, value });
+        }
+        self.confidence = confidence;
+        Ok(self)
+    }
+
+    /// Marks which questions the user starred, one flag per response.
+    pub fn with_important(mut self, important: Vec<bool>) -> Result<Self, NemesisError> {
+        if important.len() != self.responses.len() {
+            return Err(NemesisError::MismatchedLengths {
+                expected: self.responses.len(),
+                got: important.len(),
+            });
+        }
+        self.important = important;
+        Ok(self)
+    }
+
+    /// Whether the user starred `question`.
+    pub fn is_important(&self, question: usize) -> bool {
+        self.important.get(question).copied().unwrap_or(false)
+    }
+
+    /// Confidence in answer `question`, or 1.0 if none was recorded.
+    pub fn confidence_at(&self, question: usize) -> f64 {
+        self.confidence.get(question).copied().unwrap_or(1.0)
+    }
+
+    /// A copy of this user with a different answer sheet, skipping validation.
+    ///
+    /// Used by scorers that compare transformed or reduced responses.
+    /// Confidences and stars are kept only if the new sheet has the same
+    /// length, since otherwise they would no longer line up with the answers.
+    pub(crate) fn with_responses(&self, responses: Vec<i32>) -> User {
+        let aligned = responses.len() == self.responses.len();
+        User {
+            id: self.id.clone(),
+            responses,
+            schema_version: self.schema_version,
+            tags: self.tags.clone(),
+            confidence: if aligned {
+                self.confidence.clone()
+            } else {
+                Vec::new()
+            },
+            important: if aligned {
+                self.important.clone()
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    /// Total distance of this user's answers from the neutral midpoint.
+    ///
+    /// 0 for someone who answered everything neutrally; higher the more
+    /// strongly they hold their views.
+    pub fn engagement(&self) -> f64 {
+        self.responses
+            .iter()
+            .map(|&r| (r as f64 - NEUTRAL_RESPONSE as f64).abs())
+            .sum()
+    }
+
+    /// Number of questions this user answered.
+    pub fn num_questions(&self) -> usize {
+        self.responses.len()
+    }
+}
+
+/// A participant whose questionnaire may have skipped questions (`None`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartialUser {
+    #[serde(deserialize_with = "deserialize_id")]
+    pub id: String,
+    pub responses: Vec<Option<i32>>,
+    /// Carried over to the completed [`User`]; see [`User::tags`].
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tags: HashMap<String, String>,
+    /// Carried over to the completed [`User`]; see [`User::confidence`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub confidence: Vec<f64>,
+    /// Carried over to the completed [`User`]; see [`User::important`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub important: Vec<bool>,
+}
+
+impl PartialUser {
+    /// Creates a partial user; the id is checked and trimmed as for
+    /// [`User::new`], and answered questions must be on the scale.
+    pub fn new(id: impl Into<String>, responses: Vec<Option<i32>>) -> Result<Self, NemesisError> {
+        let id = validate_id(id.into())?;
+        if responses.is_empty() {
+            return Err(NemesisError::EmptyResponses);
+        }
+        for (index, value) in responses.iter().enumerate() {
+            if let Some(value) = *value {
+                if !(MIN_RESPONSE..=MAX_RESPONSE).contains(&value) {
+                    return Err(NemesisError::InvalidResponse { index, value });
+                }
+            }
+        }
+        Ok(PartialUser {
+            id,
+            responses,
+            tags: HashMap::new(),
+            confidence: Vec::new(),
+            important: Vec::new(),
+        })
+    }
+
+    /// Mean of the questions this user did answer, if any.
+    fn answered_mean(&self) -> Option<f64> {
+        let answered: Vec<f64> = self.responses.iter().flatten().map(|&r| r as f64).collect();
+        if answered.is_empty() {
+            return None;
+        }
+        Some(answered.iter().sum::<f64>() / answered.len() as f64)
+    }
+}
+
+impl<S: Into<String>> TryFrom<(S, Vec<i32>)> for User {
+    type Error = NemesisError;
+
+    /// [`User::new`] from an `(id, responses)` pair.
+    fn try_from((id, responses): (S, Vec<i32>)) -> Result<Self, Self::Error> {
+        User::new(id, responses)
+    }
+}
+
+impl From<User> for PartialUser {
+    /// Every answer marked as given, keeping tags, confidence and stars.
+    fn from(user: User) -> Self {
+        PartialUser {
+            id: user.id,
+            responses: user.responses.into_iter().map(Some).collect(),
+            tags: user.tags,
+            confidence: user.confidence,
+            important: user.important,
+        }
+    }
+}
+
+impl TryFrom<PartialUser> for User {
+    type Error = NemesisError;
+
+    /// Succeeds only when nothing was skipped, erroring with
+    /// [`NemesisError::MissingResponse`] at the first gap; use
+    /// [`impute_missing`] to fill gaps instead. Tags, confidence and stars
+    /// are carried across and checked as by [`User::with_confidence`] and
+    /// [`User::with_important`].
+    fn try_from(partial: PartialUser) -> Result<Self, Self::Error> {
+        let responses = partial
+            .responses
+            .iter()
+            .enumerate()
+            .map(|(index, r)| r.ok_or(NemesisError::MissingResponse { index }))
+            .collect::<Result<Vec<i32>, _>>()?;
+        let mut user = User::new(partial.id, responses)?;
+        user.tags = partial.tags;
+        if !partial.confidence.is_empty() {
+            user = user.with_confidence(partial.confidence)?;
+        }
+        if !partial.important.is_empty() {
+            user = user.with_important(partial.important)?;
+        }
+        Ok(user)
+    }
+}
+
+/// How [`impute_missing`] fills skipped questions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Imputation {
+    /// The mean answer to that question among users who answered it.
+    PopulationMean,
+    /// The neutral midpoint of the scale.
+    Neutral,
+    /// The user's own mean answer across the questions they did answer.
+    PerUserMean,
+}
+
+/// Fills every skipped answer in place and returns the completed users.
+///
+/// Imputed values are rounded to the nearest answer and clamped to the scale.
+/// When a mean can't be computed (nobody answered the question, or the user
+/// answered nothing) the neutral answer is used instead. Errors with
+/// [`NemesisError::InvalidId`], before filling anything, if a user's id is
+/// blank.
+pub fn impute_missing(
+    users: &mut [PartialUser],
+    strategy: Imputation,
+) -> Result<Vec<User>, NemesisError> {
+    let ids = users
+        .iter()
+        .map(|u| validate_id(u.id.clone()))
+        .collect::<Result<Vec<String>, _>>()?;
+    let num_questions = users.iter().map(|u| u.responses.len()).max().unwrap_or(0);
+    let population_means: Vec<Option<f64>> = (0..num_questions)
+        .map(|q| {
+            let answered: Vec<f64> = users
+                .iter()
+                .filter_map(|u| u.responses.get(q).copied().flatten())
+                .map(|r| r as f64)
+                .collect();
+            (!answered.is_empty()).then(|| answered.iter().sum::<f64>() / answered.len() as f64)
+        })
+        .collect();
+
+    for user in users.iter_mut() {
+        let user_mean = user.answered_mean();
+        for (q, slot) in user.responses.iter_mut().enumerate() {
+            if slot.is_some() {
+                continue;
+            }
+            let fill = match strategy {
+                Imputation::PopulationMean => population_means[q],
+                Imputation::Neutral => None,
+                Imputation::PerUserMean => user_mean,
+            };
+            let value = fill.map_or(NEUTRAL_RESPONSE, |m| m.round() as i32);
+            *slot = Some(value.clamp(MIN_RESPONSE, MAX_RESPONSE));
+        }
+    }
+
+    Ok(users
+        .iter()
+        .zip(ids)
+        .map(|(u, id)| User {
+            tags: u.tags.clone(),
+            confidence: u.confidence.clone(),
+            important: u.important.clone(),
+            ..User::unchecked(id, u.responses.iter().flatten().copied().collect())
+        })
+        .collect())
+}
+
+/// Trims surrounding whitespace from a user id, rejecting ids that are then
+/// empty with [`NemesisError::InvalidId`] carrying the original.
+pub fn validate_id(id: String) -> Result<String, NemesisError> {
+    let trimmed = id.trim();
+    if trimmed.is_empty() {
+        return Err(NemesisError::InvalidId(id));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// [`validate_id`] for serde, so deserialized users get the same id rules
+/// as constructed ones.
+pub(crate) fn deserialize_id<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    validate_id(String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+}
+
+/// Checks that `responses` is non-empty and every answer is on the scale.
+pub fn validate_responses(responses: &[i32]) -> Result<(), NemesisError> {
+    if responses.is_empty() {
+        return Err(NemesisError::EmptyResponses);
+    }
+    for (index, &value) in responses.iter().enumerate() {
+        if !(MIN_RESPONSE..=MAX_RESPONSE).contains(&value) {
+            return Err(NemesisError::InvalidResponse { index, value });
+        }
+    }
+    Ok(())
+}
+
+/// Loads a stored user payload of any known schema version and upgrades it
+/// to [`CURRENT_SCHEMA_VERSION`].
+///
+/// Version 1 payloads (no `schema_version` field) carry only `id` and
+/// `responses`. Ids are checked while deserializing and responses are
+/// re-validated after migration, and
+/// payloads from a newer, unknown version are rejected rather than guessed at.
+pub fn migrate_user(value: serde_json::Value) -> Result<User, NemesisError> {
+    let mut user: User =
+        serde_json::from_value(value).map_err(|e| NemesisError::Deserialization(e.to_string()))?;
+    if user.schema_version > CURRENT_SCHEMA_VERSION {
+        return Err(NemesisError::UnsupportedSchemaVersion(user.schema_version));
+    }
+    validate_responses(&user.responses)?;
+    user.schema_version = CURRENT_SCHEMA_VERSION;
+    Ok(user)
+}
+
+/// How [`dedupe_users`] collapses several submissions sharing one id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DedupePolicy {
+    /// Keep the earliest submission.
+    KeepFirst,
+    /// Keep the latest submission.
+    KeepLast,
+    /// Average each answer across submissions, rounded to the nearest valid answer.
+    Average,
+}
+
+/// Collapses duplicate submissions so every id appears once.
+///
+/// Output order follows each id's first appearance. Under
+/// [`DedupePolicy::Average`], an id whose submissions disagree on the number of
+/// questions can't be averaged meaningfully and is skipped entirely.
+pub fn dedupe_users(users: Vec<User>, policy: DedupePolicy) -> Vec<User> {
+    let mut order: Vec<String> = Vec::new();
+    let mut grouped: HashMap<String, Vec<User>> = HashMap::new();
+    for user in users {
+        let entry = grouped.entry(user.id.clone()).or_default();
+        if entry.is_empty() {
+            order.push(user.id.clone());
+        }
+        entry.push(user);
+    }
+
+    order
+        .into_iter()
+        .filter_map(|id| {
+            let mut submissions = grouped.remove(&id)?;
+            match policy {
+                DedupePolicy::KeepFirst => Some(submissions.swap_remove(0)),
+                DedupePolicy::KeepLast => submissions.pop(),
+                DedupePolicy::Average => average_submissions(submissions),
+            }
+        })
+        .collect()
+}
+
+fn average_submissions(mut submissions: Vec<User>) -> Option<User> {
+    let len = submissions[0].responses.len();
+    if submissions.iter().any(|u| u.responses.len() != len) {
+        return None;
+    }
+    let count = submissions.len() as f64;
+    let averaged = (0..len)
+        .map(|q| {
+            let mean = submissions
+                .iter()
+                .map(|u| u.responses[q] as f64)
+                .sum::<f64>()
+                / count;
+            (mean.round() as i32).clamp(MIN_RESPONSE, MAX_RESPONSE)
+        })
+        .collect();
+    let mut user = submissions.swap_remove(0);
+    user.responses = averaged;
+    Some(user)
+}
+
+/// Checks a whole pool before matching and reports every problem found.
+///
+/// Unlike [`User::new`], this doesn't stop at the first issue: each user's
+/// responses are checked for range and emptiness, every user must have as
+/// many answers as the first user, and each repeated id is reported once.
+pub fn validate_pool(users: &[User]) -> Result<(), Vec<NemesisError>> {
+    let mut errors = Vec::new();
+    let expected = users.first().map_or(0, User::num_questions);
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut reported: HashSet<&str> = HashSet::new();
+    for user in users {
+        let invalid = |error| NemesisError::InvalidUser {
+            id: user.id.clone(),
+            error: Box::new(error),
+        };
+        if !seen.insert(&user.id) && reported.insert(&user.id) {
+            errors.push(NemesisError::DuplicateUserId(user.id.clone()));
+        }
+        if user.num_questions() != expected {
+            errors.push(invalid(NemesisError::MismatchedLengths {
+                expected,
+                got: user.num_questions(),
+            }));
+        }
+        if user.responses.is_empty() {
+            errors.push(invalid(NemesisError::EmptyResponses));
+        }
+        for (index, &value) in user.responses.iter().enumerate() {
+            if !(MIN_RESPONSE..=MAX_RESPONSE).contains(&value) {
+                errors.push(invalid(NemesisError::InvalidResponse { index, value }));
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use common::user;
+use nemesis_finder::analysis::{
+    expected_opposition, find_near_duplicates, most_divisive_candidate, question_means,
+    value_opposition_profile,
+};
+use nemesis_finder::testing::random_users;
+use nemesis_finder::{controversy_scores, NemesisError, SimpleDifferenceScorer};
+
+#[test]
+fn extremist_is_more_controversial_than_neutral_user() {
+    let users = vec![
+        user("extreme", &[1, 7, 1, 7]),
+        user("neutral", &[4, 4, 4, 4]),
+        user("mild", &[3, 5, 4, 4]),
+    ];
+    let scores = controversy_scores(&users);
+    assert_eq!(scores.len(), 3);
+    assert!(scores["extreme"] > scores["mild"]);
+    assert!(scores["extreme"] > scores["neutral"]);
+}
+
+#[test]
+fn controversy_is_distance_from_question_means() {
+    let users = vec![user("a", &[1, 1]), user("b", &[7, 7]), user("c", &[4, 4])];
+    assert_eq!(question_means(&users), vec![4.0, 4.0]);
+    let scores = controversy_scores(&users);
+    assert_eq!(scores["a"], 6.0);
+    assert_eq!(scores["b"], 6.0);
+    assert_eq!(scores["c"], 0.0);
+}
+
+#[test]
+fn most_divisive_candidate_picks_the_question_that_splits_the_pool() {
+    let users = vec![
+        user("a", &[1, 4]),
+        user("b", &[7, 4]),
+        user("c", &[2, 5]),
+        user("d", &[6, 3]),
+    ];
+    let candidates = vec![
+        vec![4, 4, 4, 4], // consensus
+        vec![3, 5, 4, 4], // mild spread
+        vec![1, 7, 1, 7], // even split
+        vec![1, 1, 1, 7], // one dissenter
+    ];
+    assert_eq!(most_divisive_candidate(&users, &candidates), Ok(Some(2)));
+    assert_eq!(most_divisive_candidate(&users, &[]), Ok(None));
+}
+
+#[test]
+fn most_divisive_candidate_deepens_existing_divides() {
+    // The pool already splits a+b against c+d.
+    let users = vec![
+        user("a", &[1]),
+        user("b", &[1]),
+        user("c", &[7]),
+        user("d", &[7]),
+    ];
+    // Both candidates have the same mean gap, but only the second one lines
+    // up with the existing split and spreads the pair scores further apart.
+    let candidates = vec![vec![1, 7, 1, 7], vec![1, 1, 7, 7]];
+    assert_eq!(most_divisive_candidate(&users, &candidates), Ok(Some(1)));
+}
+
+#[test]
+fn most_divisive_candidate_rejects_wrong_lengths() {
+    let users = vec![user("a", &[1]), user("b", &[7])];
+    assert_eq!(
+        most_divisive_candidate(&users, &[vec![1, 7], vec![4, 4, 4]]),
+        Err(NemesisError::MismatchedLengths {
+            expected: 2,
+            got: 3
+        })
+    );
+}
+
+#[test]
+fn extreme_answers_contribute_more_opposition() {
+    let users = random_users(30, 6, 11);
+    let profile = value_opposition_profile(&users, &SimpleDifferenceScorer).unwrap();
+    assert_eq!(profile[0], 0.0);
+    assert!(profile[1] > profile[4], "{profile:?}");
+    assert!(profile[7] > profile[4], "{profile:?}");
+}
+
+#[test]
+fn value_profile_averages_per_occurrence() {
+    // One question: gaps 1-4 = 3, 1-7 = 6, 4-7 = 3.
+    let users = vec![user("a", &[1]), user("b", &[4]), user("c", &[7])];
+    let profile = value_opposition_profile(&users, &SimpleDifferenceScorer).unwrap();
+    assert_eq!(profile, [0.0, 4.5, 0.0, 0.0, 3.0, 0.0, 0.0, 4.5]);
+}
+
+#[test]
+fn near_duplicates_are_flagged() {
+    let users = vec![
+        user("orig", &[1, 5, 7, 2, 3]),
+        user("distinct", &[7, 2, 1, 6, 5]),
+        user("copy", &[1, 5, 7, 2, 4]),
+    ];
+    let suspects = find_near_duplicates(&users, 2.0, &SimpleDifferenceScorer);
+    assert_eq!(suspects, vec![("orig".to_string(), "copy".to_string())]);
+    assert!(find_near_duplicates(&users, 1.0, &SimpleDifferenceScorer).is_empty());
+}
+
+#[test]
+fn expected_opposition_averages_over_everyone_else() {
+    let pool = vec![
+        user("me", &[1, 4]),
+        user("x", &[7, 4]),
+        user("y", &[1, 1]),
+        user("z", &[2, 5]),
+    ];
+    // Gaps: x = 6, y = 3, z = 2.
+    assert_eq!(
+        expected_opposition(&pool[0], &pool, &SimpleDifferenceScorer),
+        11.0 / 3.0
+    );
+    assert_eq!(
+        expected_opposition(&pool[0], &pool[..1], &SimpleDifferenceScorer),
+        0.0
+    );
+}
@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+use nemesis_finder::matcher::ApproxMatcher;
+use nemesis_finder::testing::random_users;
+use nemesis_finder::{Match, NemesisMatcher, RecordingScorer, SimpleDifferenceScorer};
+
+fn total(matches: &[Match]) -> f64 {
+    matches.iter().map(|m| m.score).sum()
+}
+
+#[test]
+fn approx_total_is_close_to_exact_greedy() {
+    let users = random_users(80, 12, 2024);
+    let exact = NemesisMatcher::new(SimpleDifferenceScorer)
+        .find_matches(&users)
+        .unwrap();
+    let approx_matcher = ApproxMatcher::new(SimpleDifferenceScorer, 10);
+    let approx = approx_matcher.find_matches(&users);
+
+    assert_eq!(approx.len(), exact.len());
+    let ratio = total(&approx) / total(&exact);
+    assert!(
+        ratio > 0.9,
+        "approx reached only {ratio:.3} of exact greedy"
+    );
+    assert!(approx_matcher.candidate_pairs(&users).len() <= 80 * 10);
+}
+
+#[test]
+fn approx_matches_everyone_once() {
+    let users = random_users(31, 8, 5);
+    let matches = ApproxMatcher::new(SimpleDifferenceScorer, 3).find_matches(&users);
+    assert_eq!(matches.len(), 15);
+    let mut seen = HashSet::new();
+    for m in &matches {
+        assert!(seen.insert(m.user1_id.clone()));
+        assert!(seen.insert(m.user2_id.clone()));
+    }
+}
+
+#[test]
+fn approx_with_full_k_equals_exact_greedy() {
+    let users = random_users(12, 6, 9);
+    let exact = NemesisMatcher::new(SimpleDifferenceScorer)
+        .find_matches(&users)
+        .unwrap();
+    let approx = ApproxMatcher::new(SimpleDifferenceScorer, users.len()).find_matches(&users);
+    assert_eq!(approx, exact);
+}
+
+#[test]
+fn bounded_heap_large_enough_is_exact() {
+    let users = random_users(40, 10, 11);
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let all_pairs = 40 * 39 / 2;
+    assert_eq!(
+        matcher.find_matches_bounded(&users, all_pairs),
+        matcher.find_matches(&users).unwrap()
+    );
+    assert_eq!(matcher.top_scored_pairs(&users, all_pairs).len(), all_pairs);
+}
+
+#[test]
+fn bounded_heap_keeps_the_best_pairs() {
+    let users = random_users(30, 10, 3);
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let mut all: Vec<f64> = matcher
+        .calculate_all_pairs(&users)
+        .iter()
+        .map(|p| p.2)
+        .collect();
+    all.sort_by(|a, b| b.total_cmp(a));
+    let top: Vec<f64> = matcher
+        .top_scored_pairs(&users, 25)
+        .iter()
+        .map(|p| p.2)
+        .collect();
+    assert_eq!(top, all[..25].to_vec());
+}
+
+#[test]
+fn small_heap_still_matches_everyone() {
+    let users = random_users(21, 6, 8);
+    let matches = NemesisMatcher::new(SimpleDifferenceScorer).find_matches_bounded(&users, 5);
+    assert_eq!(matches.len(), 10);
+    let mut seen = HashSet::new();
+    for m in &matches {
+        assert!(seen.insert(m.user1_id.clone()) && seen.insert(m.user2_id.clone()));
+    }
+}
+
+#[test]
+fn bounded_matching_scores_most_pairs_once() {
+    let users = random_users(41, 6, 8);
+    let matcher = NemesisMatcher::new(RecordingScorer::new(SimpleDifferenceScorer));
+    assert_eq!(matcher.find_matches_bounded(&users, 10).len(), 20);
+    // One full pass is 820 pairs; later passes only rescore leftovers.
+    let calls = matcher.scorer().records().len();
+    assert!(calls < 820 * 3 / 2, "{calls} scorer calls");
+}
@@ -0,0 +1,20 @@
+#![cfg(feature = "tokio")]
+
+mod common;
+
+use common::user;
+use nemesis_finder::{NemesisMatcher, SimpleDifferenceScorer};
+
+#[tokio::test]
+async fn async_matching_agrees_with_sync() {
+    let users = vec![
+        user("a", &[1, 1, 4]),
+        user("b", &[7, 7, 4]),
+        user("c", &[4, 4, 1]),
+        user("d", &[4, 2, 7]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let expected = matcher.find_matches_blocking(&users);
+    assert_eq!(expected, matcher.find_matches(&users).unwrap());
+    assert_eq!(matcher.find_matches_async(users).await, expected);
+}
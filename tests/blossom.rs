@@ -0,0 +1,70 @@
+use nemesis_finder::matcher::blossom::max_weight_matching;
+
+fn lcg(state: &mut u64) -> u64 {
+    *state = state
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    *state >> 33
+}
+
+/// Exhaustive best total over perfect (or near-perfect) matchings of a complete graph.
+fn brute_force(n: usize, w: &[Vec<f64>], free: &mut Vec<bool>) -> f64 {
+    let Some(i) = (0..n).find(|&i| free[i]) else {
+        return 0.0;
+    };
+    free[i] = false;
+    let remaining = free.iter().filter(|f| **f).count();
+    let mut best = f64::NEG_INFINITY;
+    if remaining % 2 == 0 {
+        // `i` may sit out only when the rest can pair up perfectly.
+        best = brute_force(n, w, free);
+    }
+    for j in (i + 1)..n {
+        if free[j] {
+            free[j] = false;
+            best = best.max(w[i][j] + brute_force(n, w, free));
+            free[j] = true;
+        }
+    }
+    free[i] = true;
+    best
+}
+
+#[test]
+fn matches_brute_force_on_random_complete_graphs() {
+    let mut seed = 7;
+    for n in 2..=9 {
+        for _ in 0..30 {
+            let mut edges = Vec::new();
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    edges.push((i, j, (lcg(&mut seed) % 100) as f64 / 4.0));
+                }
+            }
+            let mut w = vec![vec![0.0; n]; n];
+            for &(i, j, weight) in &edges {
+                w[i][j] = weight;
+            }
+            let mate = max_weight_matching(n, &edges, true);
+            let total: f64 = (0..n)
+                .filter_map(|i| mate[i].filter(|&j| i < j).map(|j| w[i][j]))
+                .sum();
+            assert_eq!(mate.iter().filter(|m| m.is_none()).count(), n % 2);
+            let expected = brute_force(n, &w, &mut vec![true; n]);
+            assert!(
+                (total - expected).abs() < 1e-6,
+                "n={n}: {total} vs {expected}"
+            );
+        }
+    }
+}
+
+#[test]
+fn mates_are_symmetric() {
+    let edges = [(0, 1, 5.0), (1, 2, 11.0), (2, 3, 5.0), (0, 3, 1.0)];
+    let mate = max_weight_matching(4, &edges, true);
+    for (i, m) in mate.iter().enumerate() {
+        let j = m.expect("perfect matching exists");
+        assert_eq!(mate[j], Some(i));
+    }
+}
@@ -0,0 +1,16 @@
+#![allow(dead_code)]
+
+use nemesis_finder::User;
+
+pub fn user(id: &str, responses: &[i32]) -> User {
+    User::new(id, responses.to_vec()).expect("valid test user")
+}
+
+/// Unordered id pair, handy for comparing matchings regardless of orientation.
+pub fn pair_key(a: &str, b: &str) -> (String, String) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
+}
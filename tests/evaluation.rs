@@ -0,0 +1,239 @@
+mod common;
+
+use common::user;
+use nemesis_finder::evaluation::{
+    best_strategy_matching, marginal_user_value, matched_question_opposition,
+    matching_assortativity, matching_diff, optimality_gap, rank_correlation, stability_under_noise,
+    suggest_swaps, topic_coverage, total_score,
+};
+use nemesis_finder::testing::random_users;
+use nemesis_finder::{
+    CosineSimilarityScorer, InvertScorer, Match, NemesisMatcher, ScoringStrategy,
+    SimpleDifferenceScorer, WeightedScorer,
+};
+
+#[test]
+fn optimality_gap_on_myopic_instance() {
+    // Greedy takes a-b (12) then c-d (0); optimal pairs a-c and b-d for 18.
+    let users = vec![
+        user("a", &[1, 1, 4]),
+        user("b", &[7, 7, 4]),
+        user("c", &[4, 4, 1]),
+        user("d", &[4, 4, 1]),
+    ];
+    let gap = optimality_gap(&users, &SimpleDifferenceScorer);
+    assert!((gap - 1.0 / 3.0).abs() < 1e-12, "gap was {gap}");
+}
+
+#[test]
+fn optimality_gap_is_zero_when_greedy_is_optimal() {
+    let users = vec![
+        user("a", &[1]),
+        user("b", &[7]),
+        user("c", &[2]),
+        user("d", &[6]),
+    ];
+    assert_eq!(optimality_gap(&users, &SimpleDifferenceScorer), 0.0);
+    let clones = vec![user("a", &[4]), user("b", &[4])];
+    assert_eq!(optimality_gap(&clones, &SimpleDifferenceScorer), 0.0);
+}
+
+#[test]
+fn identical_scorers_are_perfectly_correlated() {
+    let users = vec![
+        user("a", &[1, 5, 2]),
+        user("b", &[7, 3, 4]),
+        user("c", &[4, 4, 6]),
+        user("d", &[2, 7, 1]),
+    ];
+    let r = rank_correlation(&users, &SimpleDifferenceScorer, &SimpleDifferenceScorer);
+    assert!((r - 1.0).abs() < 1e-12, "r was {r}");
+}
+
+#[test]
+fn different_scorers_are_less_correlated() {
+    let users = vec![
+        user("a", &[1, 5, 2]),
+        user("b", &[7, 3, 4]),
+        user("c", &[4, 4, 6]),
+        user("d", &[2, 7, 1]),
+        user("e", &[5, 5, 5]),
+    ];
+    let inverted = InvertScorer::new(SimpleDifferenceScorer, 18.0);
+    let r = rank_correlation(&users, &SimpleDifferenceScorer, &inverted);
+    assert!((r + 1.0).abs() < 1e-12, "r was {r}");
+
+    let cosine = rank_correlation(&users, &SimpleDifferenceScorer, &CosineSimilarityScorer);
+    assert!(cosine < 1.0, "r was {cosine}");
+}
+
+#[test]
+fn optimal_matching_is_more_assortative_than_random() {
+    let users = random_users(20, 8, 11);
+    let scorer = SimpleDifferenceScorer;
+    let optimal = NemesisMatcher::new(scorer).find_matches_optimal(&users);
+    // Neighbours in generation order: an arbitrary, unoptimised pairing.
+    let arbitrary: Vec<Match> = users
+        .chunks(2)
+        .map(|pair| Match::new(pair[0].id.clone(), pair[1].id.clone(), 0.0))
+        .collect();
+
+    let optimal_a = matching_assortativity(&optimal, &users, &scorer);
+    let arbitrary_a = matching_assortativity(&arbitrary, &users, &scorer);
+    assert!(optimal_a > 1.0, "optimal was {optimal_a}");
+    assert!(optimal_a > arbitrary_a, "{optimal_a} vs {arbitrary_a}");
+    assert_eq!(matching_assortativity(&[], &users, &scorer), 0.0);
+}
+
+#[test]
+fn matched_question_opposition_averages_over_matched_pairs_only() {
+    let users = vec![
+        user("a", &[1, 4, 2]),
+        user("b", &[7, 4, 3]),
+        user("c", &[2, 6, 7]),
+        user("d", &[5, 1, 7]),
+        user("bystander", &[7, 7, 7]),
+    ];
+    let matches = vec![
+        Match::new("a", "b", 0.0),
+        Match::new("c", "d", 0.0),
+        Match::new("a", "ghost", 0.0),
+    ];
+    // Question 0: (6 + 3) / 2, question 1: (0 + 5) / 2, question 2: (1 + 0) / 2.
+    assert_eq!(
+        matched_question_opposition(&matches, &users),
+        vec![4.5, 2.5, 0.5]
+    );
+    assert!(matched_question_opposition(&[], &users).is_empty());
+}
+
+#[test]
+fn best_strategy_matching_keeps_the_most_opposed_strategy() {
+    let users = vec![
+        user("a", &[1, 1]),
+        user("b", &[7, 7]),
+        user("c", &[4, 1]),
+        user("d", &[4, 7]),
+    ];
+    // Both pair a-b and c-d. Relative to its maximum pair score, simple
+    // difference rates them 1 + 0.5; the weighted scorer ignores the second
+    // question and only sees 1 + 0.
+    let strategies: Vec<Box<dyn ScoringStrategy>> = vec![
+        Box::new(WeightedScorer::new(vec![1.0, 0.0]).unwrap()),
+        Box::new(SimpleDifferenceScorer),
+    ];
+    let (name, matches) = best_strategy_matching(&users, strategies);
+    assert_eq!(name, "SimpleDifference");
+    assert_eq!(total_score(&matches), 18.0);
+
+    assert_eq!(
+        best_strategy_matching(&users, Vec::new()),
+        (String::new(), Vec::new())
+    );
+}
+
+#[test]
+fn suggest_swaps_finds_the_one_beneficial_swap() {
+    let users = vec![
+        user("a", &[1, 1]),
+        user("b", &[2, 2]),
+        user("c", &[7, 7]),
+        user("d", &[6, 6]),
+        user("e", &[1, 7]),
+        user("f", &[7, 1]),
+    ];
+    // a–b and c–d pair near-twins; a–c, b–d (or a–d, b–c) is far better.
+    // e–f is already maximally opposed, so no swap involving it helps.
+    let matches = vec![
+        Match::new("a", "b", 2.0),
+        Match::new("c", "d", 2.0),
+        Match::new("e", "f", 12.0),
+    ];
+    let swaps = suggest_swaps(&matches, &users, &SimpleDifferenceScorer);
+    assert_eq!(swaps, vec![(matches[0].clone(), matches[1].clone())]);
+
+    let fixed = vec![
+        Match::new("a", "c", 12.0),
+        Match::new("b", "d", 8.0),
+        Match::new("e", "f", 12.0),
+    ];
+    assert!(suggest_swaps(&fixed, &users, &SimpleDifferenceScorer).is_empty());
+}
+
+#[test]
+fn clear_opposites_are_stable_under_noise() {
+    let users = vec![
+        user("a", &[1, 1, 1, 1, 1]),
+        user("b", &[7, 7, 7, 7, 7]),
+        user("c", &[1, 7, 1, 7, 1]),
+        user("d", &[7, 1, 7, 1, 7]),
+    ];
+    let stable = stability_under_noise(&users, &SimpleDifferenceScorer, 50, 7);
+    assert_eq!(stable, 1.0);
+
+    // Near-identical answers: every pairing scores about the same, so noise
+    // reshuffles who gets whom.
+    let users = vec![
+        user("a", &[3, 4, 4, 4, 5]),
+        user("b", &[4, 3, 4, 5, 4]),
+        user("c", &[4, 4, 5, 4, 3]),
+        user("d", &[5, 4, 3, 4, 4]),
+    ];
+    let ambiguous = stability_under_noise(&users, &SimpleDifferenceScorer, 50, 7);
+    assert!(ambiguous < 0.8, "ambiguous pool kept {ambiguous}");
+    assert_eq!(
+        ambiguous,
+        stability_under_noise(&users, &SimpleDifferenceScorer, 50, 7)
+    );
+}
+
+#[test]
+fn topic_coverage_counts_distinct_strong_clashes() {
+    let users = vec![
+        user("a", &[1, 4, 4, 1, 4]),
+        user("b", &[7, 4, 5, 6, 4]),
+        user("c", &[4, 1, 4, 2, 4]),
+        user("d", &[4, 6, 4, 7, 4]),
+    ];
+    let matches = vec![Match::new("a", "b", 12.0), Match::new("c", "d", 10.0)];
+    // Questions 0 (a–b), 1 (c–d) and 3 (both) clash by 5 or more; 2 and 4 don't.
+    assert_eq!(topic_coverage(&matches, &users, 5), 3);
+    assert_eq!(topic_coverage(&matches, &users, 6), 1);
+    assert_eq!(topic_coverage(&matches[..1], &users, 1), 3);
+
+    // Off-scale answers can still arrive through the public field.
+    let mut off_scale = users.clone();
+    off_scale[0].responses[2] = i32::MIN;
+    off_scale[1].responses[2] = i32::MAX;
+    assert_eq!(topic_coverage(&matches, &off_scale, 5), 4);
+}
+
+#[test]
+fn contrarian_candidate_adds_more_than_neutral_one() {
+    let pool = vec![user("a", &[1, 1]), user("b", &[7, 7]), user("c", &[4, 4])];
+    let scorer = SimpleDifferenceScorer;
+    // Alone, c sits out; the contrarian pairs with c for 6, the neutral for 1.
+    assert_eq!(
+        marginal_user_value(&pool, &user("contrarian", &[1, 7]), &scorer),
+        6.0
+    );
+    assert_eq!(
+        marginal_user_value(&pool, &user("neutral", &[4, 5]), &scorer),
+        1.0
+    );
+}
+
+#[test]
+fn matching_diff_reports_stayed_broken_and_formed_pairs() {
+    let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+    let old = vec![Match::new("a", "b", 5.0), Match::new("c", "d", 4.0)];
+    // b–a is the same pair reversed; d now faces late signup e.
+    let new = vec![Match::new("b", "a", 5.0), Match::new("d", "e", 6.0)];
+    let diff = matching_diff(&old, &new);
+    assert_eq!(diff.stayed, vec![pair("a", "b")]);
+    assert_eq!(diff.broken, vec![pair("c", "d")]);
+    assert_eq!(diff.formed, vec![pair("d", "e")]);
+    let mut affected: Vec<&str> = diff.affected_ids().into_iter().collect();
+    affected.sort();
+    assert_eq!(affected, vec!["c", "d", "e"]);
+}
@@ -0,0 +1,19 @@
+use nemesis_finder::export::matches_to_dot;
+use nemesis_finder::Match;
+
+#[test]
+fn dot_has_one_labelled_edge_per_match() {
+    let matches = vec![Match::new("a", "b", 42.5), Match::new("c", "d", 6.0)];
+    let dot = matches_to_dot(&matches);
+    assert!(dot.starts_with("graph {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert_eq!(dot.lines().filter(|l| l.contains(" -- ")).count(), 2);
+    assert!(dot.contains("  \"a\" -- \"b\" [label=\"42.5\"];\n"));
+    assert!(dot.contains("  \"c\" -- \"d\" [label=\"6\"];\n"));
+}
+
+#[test]
+fn dot_escapes_quotes_and_backslashes_in_ids() {
+    let dot = matches_to_dot(&[Match::new("say \"hi\"", "back\\slash", 1.0)]);
+    assert!(dot.contains(r#""say \"hi\"" -- "back\\slash""#), "{dot}");
+}
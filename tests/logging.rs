@@ -0,0 +1,53 @@
+#![cfg(feature = "log")]
+
+mod common;
+
+use std::sync::Mutex;
+
+use common::user;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use nemesis_finder::{Constraints, NemesisMatcher, SimpleDifferenceScorer};
+
+/// Keeps every message from this crate for inspection.
+struct CapturingLogger(Mutex<Vec<String>>);
+
+impl Log for CapturingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Debug
+    }
+
+    fn log(&self, record: &Record) {
+        if record.target().starts_with("nemesis_finder") {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CapturingLogger = CapturingLogger(Mutex::new(Vec::new()));
+
+#[test]
+fn matcher_logs_pairs_top_score_leftovers_and_constraint_skips() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Debug);
+
+    let users = vec![user("a", &[1, 1]), user("b", &[7, 7]), user("c", &[4, 4])];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    matcher.find_matches(&users).unwrap();
+    matcher.find_matches_constrained(&users, &Constraints::new().exclude("a", "b"));
+
+    let messages = LOGGER.0.lock().unwrap().clone();
+    for expected in [
+        "scored 3 pairs among 3 users",
+        "top pair score 12",
+        "1 of 3 users left unmatched",
+        "constraints skipped 1 of 3 scored pairs",
+        "top pair score 6",
+    ] {
+        assert!(
+            messages.iter().any(|m| m == expected),
+            "missing {expected:?} in {messages:?}"
+        );
+    }
+}
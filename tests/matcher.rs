@@ -0,0 +1,1369 @@
+mod common;
+
+use std::collections::HashSet;
+
+use common::{pair_key, user};
+use nemesis_finder::matcher::{
+    count_perfect_matchings, estimate_cost, label_matches, match_blocks, minimal_question_subset,
+    partition_pool, swing_questions, unmatched_users, validate_matching, DropLast,
+    DropLowestEngagement, LeftoverPolicy, MatchBuilder, MatchOutcome, Objective, SortKey,
+    TimeClass, TournamentScheduler, WeightedRandomMatcher, MAX_COUNTED_USERS,
+};
+use nemesis_finder::testing::random_users;
+use nemesis_finder::{
+    Constraints, EuclideanDistanceScorer, Feasibility, Match, NemesisError, NemesisMatcher,
+    ScoringStrategy, SimpleDifferenceScorer, TagConstraint, User, WeightedScorer,
+};
+
+fn total(matches: &[Match]) -> f64 {
+    matches.iter().map(|m| m.score).sum()
+}
+
+#[test]
+fn greedy_pairs_opposites() {
+    let users = vec![
+        user("a", &[1, 1, 1]),
+        user("b", &[7, 7, 7]),
+        user("c", &[2, 2, 2]),
+        user("d", &[6, 6, 6]),
+    ];
+    let matches = NemesisMatcher::new(SimpleDifferenceScorer)
+        .find_matches(&users)
+        .unwrap();
+    let pairs: HashSet<_> = matches
+        .iter()
+        .map(|m| pair_key(&m.user1_id, &m.user2_id))
+        .collect();
+    assert_eq!(
+        pairs,
+        HashSet::from([pair_key("a", "b"), pair_key("c", "d")])
+    );
+}
+
+#[test]
+fn odd_pool_leaves_one_user_out() {
+    let users = vec![user("a", &[1]), user("b", &[7]), user("c", &[4])];
+    let matches = NemesisMatcher::new(SimpleDifferenceScorer)
+        .find_matches(&users)
+        .unwrap();
+    assert_eq!(matches.len(), 1);
+    let left: Vec<_> = unmatched_users(&users, &matches)
+        .iter()
+        .map(|u| u.id.clone())
+        .collect();
+    assert_eq!(left, vec!["c"]);
+}
+
+#[test]
+fn optimal_beats_greedy_when_greedy_is_myopic() {
+    // Greedy grabs a-b (12) and is then stuck with c-d (0); optimal takes 9 + 9.
+    let users = vec![
+        user("a", &[1, 1, 4]),
+        user("b", &[7, 7, 4]),
+        user("c", &[4, 4, 1]),
+        user("d", &[4, 4, 1]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let greedy = matcher.find_matches(&users).unwrap();
+    let optimal = matcher.find_matches_optimal(&users);
+    assert_eq!(total(&greedy), 12.0);
+    assert_eq!(total(&optimal), 18.0);
+    assert_eq!(optimal.len(), 2);
+}
+
+#[test]
+fn rotating_leftover_differs_across_rounds() {
+    let users: Vec<User> = vec![user("a", &[1, 1]), user("b", &[7, 7]), user("c", &[4, 4])];
+    let mut matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+
+    let mut leftovers = Vec::new();
+    for _ in 0..3 {
+        let matches = matcher.find_matches_rotating(&users);
+        assert_eq!(matches.len(), 1);
+        let left = unmatched_users(&users, &matches);
+        assert_eq!(left.len(), 1);
+        leftovers.push(left[0].id.clone());
+    }
+
+    assert_ne!(leftovers[0], leftovers[1]);
+    assert_ne!(leftovers[1], leftovers[2]);
+    let distinct: HashSet<_> = leftovers.iter().collect();
+    assert_eq!(distinct.len(), 3, "every user sits out once: {leftovers:?}");
+    assert!(matcher.drop_counts().values().all(|&c| c == 1));
+}
+
+#[test]
+fn bipartite_two_vs_two_only_pairs_across_groups() {
+    // Within-group a1-a2 would be the strongest pair, but it is forbidden.
+    let group_a = vec![user("a1", &[1, 1]), user("a2", &[7, 7])];
+    let group_b = vec![user("b1", &[2, 2]), user("b2", &[6, 6])];
+    let matches =
+        NemesisMatcher::new(SimpleDifferenceScorer).find_matches_bipartite(&group_a, &group_b);
+
+    let pairs: HashSet<_> = matches
+        .iter()
+        .map(|m| pair_key(&m.user1_id, &m.user2_id))
+        .collect();
+    assert_eq!(
+        pairs,
+        HashSet::from([pair_key("a1", "b2"), pair_key("a2", "b1")])
+    );
+    assert!(matches
+        .iter()
+        .all(|m| m.user1_id.starts_with('a') && m.user2_id.starts_with('b')));
+    assert_eq!(total(&matches), 20.0);
+}
+
+#[test]
+fn bipartite_uneven_groups_leave_extras_unmatched() {
+    let group_a = vec![user("a1", &[1]), user("a2", &[4]), user("a3", &[7])];
+    let group_b = vec![user("b1", &[1]), user("b2", &[7])];
+    let matches =
+        NemesisMatcher::new(SimpleDifferenceScorer).find_matches_bipartite(&group_a, &group_b);
+
+    assert_eq!(matches.len(), 2);
+    let pairs: HashSet<_> = matches
+        .iter()
+        .map(|m| pair_key(&m.user1_id, &m.user2_id))
+        .collect();
+    assert_eq!(
+        pairs,
+        HashSet::from([pair_key("a1", "b2"), pair_key("a3", "b1")])
+    );
+    assert!(!matches.iter().any(|m| m.involves("a2")));
+}
+
+#[test]
+fn feasibility_reports_user_with_no_allowed_partner() {
+    let users = vec![
+        user("a", &[1]),
+        user("b", &[7]),
+        user("c", &[2]),
+        user("d", &[6]),
+    ];
+    let constraints = Constraints::new()
+        .exclude("d", "a")
+        .exclude("d", "b")
+        .exclude("c", "d");
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+
+    match matcher.feasibility(&users, &constraints) {
+        Feasibility::Infeasible { unplaceable } => {
+            assert!(unplaceable.contains(&"d".to_string()), "{unplaceable:?}");
+            assert_eq!(unplaceable.len(), 2);
+        }
+        Feasibility::Feasible => panic!("d has no allowed partner"),
+    }
+    let matches = matcher.find_matches_constrained(&users, &constraints);
+    assert!(!matches.iter().any(|m| m.involves("d")));
+}
+
+#[test]
+fn feasibility_accepts_satisfiable_constraints() {
+    let users = vec![
+        user("a", &[1]),
+        user("b", &[7]),
+        user("c", &[2]),
+        user("d", &[6]),
+    ];
+    let constraints = Constraints::new()
+        .exclude("a", "b")
+        .group("a", "red")
+        .group("c", "red")
+        .min_score(1.0);
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    assert!(matcher.feasibility(&users, &constraints).is_feasible());
+
+    let pairs: HashSet<_> = matcher
+        .find_matches_constrained(&users, &constraints)
+        .iter()
+        .map(|m| pair_key(&m.user1_id, &m.user2_id))
+        .collect();
+    assert_eq!(
+        pairs,
+        HashSet::from([pair_key("a", "d"), pair_key("b", "c")])
+    );
+}
+
+#[test]
+fn feasibility_allows_single_leftover_in_odd_pool() {
+    let users = vec![user("a", &[1]), user("b", &[7]), user("c", &[4])];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    assert!(matcher
+        .feasibility(&users, &Constraints::new())
+        .is_feasible());
+    let strict = Constraints::new().min_score(10.0);
+    assert_eq!(
+        matcher.feasibility(&users, &strict),
+        Feasibility::Infeasible {
+            unplaceable: vec!["a".to_string(), "b".to_string(), "c".to_string()]
+        }
+    );
+}
+
+#[test]
+fn explained_log_records_matches_and_skips_in_order() {
+    let users = vec![
+        user("a", &[1]),
+        user("b", &[7]),
+        user("c", &[2]),
+        user("d", &[6]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let (matches, log) = matcher.find_matches_explained(&users);
+
+    assert_eq!(matches, matcher.find_matches(&users).unwrap());
+    assert_eq!(
+        log,
+        vec![
+            "matched a & b (score 6.00)",
+            "skipped a & d (score 5.00): a already matched",
+            "skipped b & c (score 5.00): b already matched",
+            "matched c & d (score 4.00)",
+            "skipped a & c (score 1.00): a and c already matched",
+            "skipped b & d (score 1.00): b and d already matched",
+        ]
+    );
+}
+
+#[test]
+fn explained_log_mentions_leftover() {
+    let users = vec![user("a", &[1]), user("b", &[7]), user("c", &[4])];
+    let (_, log) = NemesisMatcher::new(SimpleDifferenceScorer).find_matches_explained(&users);
+    assert_eq!(log.last().unwrap(), "c left unmatched");
+}
+
+#[test]
+fn best_opponent_picks_most_opposed_other_user() {
+    let target = user("me", &[1, 2, 7]);
+    let pool = vec![
+        target.clone(),
+        user("twin", &[1, 2, 7]),
+        user("nemesis", &[7, 6, 1]),
+        user("mild", &[4, 4, 4]),
+    ];
+    let best = NemesisMatcher::new(SimpleDifferenceScorer)
+        .best_opponent(&target, &pool)
+        .unwrap();
+    assert_eq!(best.user1_id, "me");
+    assert_eq!(best.user2_id, "nemesis");
+    assert_eq!(best.score, 16.0);
+}
+
+#[test]
+fn best_opponent_of_empty_pool_is_none() {
+    let target = user("me", &[1]);
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    assert_eq!(matcher.best_opponent(&target, &[]), None);
+    assert_eq!(
+        matcher.best_opponent(&target, std::slice::from_ref(&target)),
+        None
+    );
+}
+
+#[test]
+fn pool_diversity_is_mean_pair_score() {
+    let users = vec![user("a", &[1, 1]), user("b", &[7, 4]), user("c", &[4, 7])];
+    // a-b = 6 + 3 = 9, a-c = 3 + 6 = 9, b-c = 3 + 3 = 6.
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    assert_eq!(matcher.pool_diversity(&users), 8.0);
+}
+
+#[test]
+fn pool_diversity_of_tiny_pools_is_zero() {
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    assert_eq!(matcher.pool_diversity(&[]), 0.0);
+    assert_eq!(matcher.pool_diversity(&[user("a", &[1])]), 0.0);
+}
+
+#[test]
+fn score_histogram_counts_every_pair() {
+    let users = vec![
+        user("a", &[1, 1]),
+        user("b", &[7, 4]),
+        user("c", &[4, 7]),
+        user("d", &[4, 4]),
+        user("e", &[7, 7]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let histogram = matcher.score_histogram(&users, 4);
+    assert_eq!(histogram.len(), 4);
+    let n = users.len();
+    assert_eq!(
+        histogram.iter().map(|&(_, _, c)| c).sum::<usize>(),
+        n * (n - 1) / 2
+    );
+    // Scores range from 3 (b-d, b-e, ...) to 12 (a-e).
+    assert_eq!(histogram[0].0, 3.0);
+    assert_eq!(histogram[3].1, 12.0);
+    assert_eq!(histogram[3].2, 1);
+}
+
+#[test]
+fn score_histogram_degenerate_cases() {
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let same = vec![user("a", &[1]), user("b", &[7])];
+    assert_eq!(matcher.score_histogram(&same, 3), vec![(6.0, 6.0, 1)]);
+    assert!(matcher.score_histogram(&same, 0).is_empty());
+    assert!(matcher.score_histogram(&same[..1], 3).is_empty());
+}
+
+fn fixed(a: &str, b: &str) -> (String, String) {
+    (a.to_string(), b.to_string())
+}
+
+#[test]
+fn fixed_pair_is_kept_and_rest_matched_greedily() {
+    // Left alone, greedy would pair a-b and c-d.
+    let users = vec![
+        user("a", &[1]),
+        user("b", &[7]),
+        user("c", &[2]),
+        user("d", &[6]),
+    ];
+    let matches = NemesisMatcher::new(SimpleDifferenceScorer)
+        .find_matches_with_fixed(&users, &[fixed("a", "c")])
+        .unwrap();
+
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0], Match::new("a", "c", 1.0));
+    assert_eq!(
+        pair_key(&matches[1].user1_id, &matches[1].user2_id),
+        pair_key("b", "d")
+    );
+}
+
+#[test]
+fn fixed_pairs_are_validated() {
+    let users = vec![user("a", &[1]), user("b", &[7]), user("c", &[2])];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    assert_eq!(
+        matcher.find_matches_with_fixed(&users, &[fixed("a", "z")]),
+        Err(NemesisError::UnknownUserId("z".to_string()))
+    );
+    assert_eq!(
+        matcher.find_matches_with_fixed(&users, &[fixed("a", "b"), fixed("c", "a")]),
+        Err(NemesisError::DuplicateUserId("a".to_string()))
+    );
+    assert_eq!(
+        matcher.find_matches_with_fixed(&users, &[fixed("b", "b")]),
+        Err(NemesisError::DuplicateUserId("b".to_string()))
+    );
+}
+
+fn sorting_fixture() -> Vec<User> {
+    // Greedy order: y-b (6), then z-a (4), then x-c (2).
+    vec![
+        user("y", &[1]),
+        user("b", &[7]),
+        user("z", &[2]),
+        user("a", &[6]),
+        user("x", &[3]),
+        user("c", &[5]),
+    ]
+}
+
+fn summary(matches: &[Match]) -> Vec<(&str, &str, f64)> {
+    matches
+        .iter()
+        .map(|m| (m.user1_id.as_str(), m.user2_id.as_str(), m.score))
+        .collect()
+}
+
+#[test]
+fn sorted_by_score_descending() {
+    let matches = NemesisMatcher::new(SimpleDifferenceScorer)
+        .find_matches_sorted(&sorting_fixture(), SortKey::ScoreDesc);
+    assert_eq!(
+        summary(&matches),
+        vec![("b", "y", 6.0), ("a", "z", 4.0), ("c", "x", 2.0)]
+    );
+}
+
+#[test]
+fn sorted_by_score_ascending() {
+    let matches = NemesisMatcher::new(SimpleDifferenceScorer)
+        .find_matches_sorted(&sorting_fixture(), SortKey::ScoreAsc);
+    assert_eq!(
+        summary(&matches),
+        vec![("c", "x", 2.0), ("a", "z", 4.0), ("b", "y", 6.0)]
+    );
+}
+
+#[test]
+fn sorted_by_first_user_id() {
+    let matches = NemesisMatcher::new(SimpleDifferenceScorer)
+        .find_matches_sorted(&sorting_fixture(), SortKey::User1Id);
+    assert_eq!(
+        summary(&matches),
+        vec![("a", "z", 4.0), ("b", "y", 6.0), ("c", "x", 2.0)]
+    );
+}
+
+#[test]
+fn must_differ_tag_rule_overrides_top_score() {
+    let users = vec![
+        user("a", &[1, 1]).with_tag("region", "north"),
+        user("b", &[7, 7]).with_tag("region", "north"),
+        user("c", &[2, 2]).with_tag("region", "south"),
+        user("d", &[6, 6]).with_tag("region", "south"),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let unconstrained: HashSet<_> = matcher
+        .find_matches(&users)
+        .unwrap()
+        .iter()
+        .map(|m| pair_key(&m.user1_id, &m.user2_id))
+        .collect();
+    assert!(unconstrained.contains(&pair_key("a", "b")));
+
+    let constraints = Constraints::new().tag_rule(TagConstraint::MustDiffer("region".into()));
+    let constrained: HashSet<_> = matcher
+        .find_matches_constrained(&users, &constraints)
+        .iter()
+        .map(|m| pair_key(&m.user1_id, &m.user2_id))
+        .collect();
+    assert_eq!(
+        constrained,
+        HashSet::from([pair_key("a", "d"), pair_key("b", "c")])
+    );
+}
+
+#[test]
+fn must_match_tag_rule_keeps_pairs_within_cohort() {
+    let users = vec![
+        user("a", &[1]).with_tag("cohort", "x"),
+        user("b", &[7]).with_tag("cohort", "y"),
+        user("c", &[6]).with_tag("cohort", "x"),
+        user("d", &[2]).with_tag("cohort", "y"),
+        user("e", &[7]),
+    ];
+    let constraints = Constraints::new().tag_rule(TagConstraint::MustMatch("cohort".into()));
+    let matches =
+        NemesisMatcher::new(SimpleDifferenceScorer).find_matches_constrained(&users, &constraints);
+    let pairs: HashSet<_> = matches
+        .iter()
+        .map(|m| pair_key(&m.user1_id, &m.user2_id))
+        .collect();
+    assert_eq!(
+        pairs,
+        HashSet::from([pair_key("a", "c"), pair_key("b", "d")])
+    );
+}
+
+#[test]
+fn partition_places_every_user_once_in_balanced_pools() {
+    let users: Vec<User> = (0..11)
+        .map(|i| user(&format!("u{i}"), &[1 + i % 7, 7 - i % 7, 4]))
+        .collect();
+    let pools = partition_pool(&users, 3);
+    assert_eq!(pools.len(), 3);
+
+    let sizes: Vec<usize> = pools.iter().map(Vec::len).collect();
+    let (min, max) = (sizes.iter().min().unwrap(), sizes.iter().max().unwrap());
+    assert!(max - min <= 1, "{sizes:?}");
+
+    let mut seen = HashSet::new();
+    for pool in &pools {
+        for u in pool {
+            assert!(seen.insert(u.id.clone()), "{} placed twice", u.id);
+        }
+    }
+    assert_eq!(seen.len(), users.len());
+}
+
+#[test]
+fn partition_spreads_extremists_across_pools() {
+    let users = vec![
+        user("x1", &[1, 1, 1]),
+        user("x2", &[7, 7, 7]),
+        user("m1", &[4, 4, 4]),
+        user("m2", &[4, 4, 5]),
+    ];
+    let pools = partition_pool(&users, 2);
+    for pool in &pools {
+        assert_eq!(pool.iter().filter(|u| u.id.starts_with('x')).count(), 1);
+    }
+    assert!(partition_pool(&users, 0).is_empty());
+}
+
+#[test]
+fn checked_matching_rejects_non_finite_scores() {
+    let scorer = WeightedScorer::new(vec![f64::MAX, f64::MAX]).unwrap();
+    let users = vec![user("a", &[1, 1]), user("b", &[7, 7]), user("c", &[4, 4])];
+    let matcher = NemesisMatcher::new(scorer);
+    match matcher.try_find_matches(&users) {
+        Err(NemesisError::NonFiniteScore {
+            user1_id,
+            user2_id,
+            score,
+        }) => {
+            assert_eq!((user1_id.as_str(), user2_id.as_str()), ("a", "b"));
+            assert!(score.is_infinite());
+        }
+        other => panic!("expected NonFiniteScore, got {other:?}"),
+    }
+}
+
+#[test]
+fn checked_matching_agrees_with_greedy_on_finite_scores() {
+    let users = vec![user("a", &[1, 1]), user("b", &[7, 7]), user("c", &[4, 4])];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    assert_eq!(
+        matcher.try_find_matches(&users).unwrap(),
+        matcher.find_matches(&users).unwrap()
+    );
+}
+
+#[test]
+fn small_pools_are_rejected_unless_lenient() {
+    let users = vec![user("alone", &[1, 7]), user("other", &[7, 1])];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    for got in [0, 1] {
+        let too_small = Err(NemesisError::PoolTooSmall { got, min: 2 });
+        assert_eq!(matcher.find_matches(&users[..got]), too_small);
+        assert_eq!(matcher.try_find_matches(&users[..got]), too_small);
+        assert!(matcher.find_matches_lenient(&users[..got]).is_empty());
+    }
+
+    let matcher = matcher.with_min_pool_size(3);
+    assert_eq!(
+        matcher.find_matches(&users),
+        Err(NemesisError::PoolTooSmall { got: 2, min: 3 })
+    );
+    assert_eq!(matcher.find_matches_lenient(&users).len(), 1);
+}
+
+#[test]
+fn zero_budget_falls_back_to_greedy() {
+    let users = vec![
+        user("a", &[1, 1, 4]),
+        user("b", &[7, 7, 4]),
+        user("c", &[4, 4, 1]),
+        user("d", &[4, 4, 1]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let outcome = matcher.find_matches_within(&users, 0);
+    assert!(!outcome.is_optimal());
+    assert_eq!(
+        outcome,
+        MatchOutcome::GreedyFallback(matcher.find_matches(&users).unwrap())
+    );
+}
+
+#[test]
+fn generous_budget_returns_the_optimal_matching() {
+    let users = vec![
+        user("a", &[1, 1, 4]),
+        user("b", &[7, 7, 4]),
+        user("c", &[4, 4, 1]),
+        user("d", &[4, 4, 1]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let outcome = matcher.find_matches_within(&users, 60_000);
+    assert!(outcome.is_optimal());
+    assert_eq!(total(outcome.matches()), 18.0);
+    assert_eq!(outcome.into_matches(), matcher.find_matches_optimal(&users));
+}
+
+#[test]
+fn tournament_never_repeats_a_pair_until_exhausted() {
+    let users = vec![
+        user("a", &[1, 1, 4]),
+        user("b", &[7, 7, 4]),
+        user("c", &[4, 4, 1]),
+        user("d", &[4, 2, 7]),
+    ];
+    let mut scheduler = TournamentScheduler::new(SimpleDifferenceScorer);
+    let mut seen = HashSet::new();
+    loop {
+        let round = scheduler.next_round(&users);
+        if round.is_empty() {
+            break;
+        }
+        assert_eq!(round.len(), 2, "every round of four should be complete");
+        for m in &round {
+            assert!(
+                seen.insert(pair_key(&m.user1_id, &m.user2_id)),
+                "{} & {} met twice",
+                m.user1_id,
+                m.user2_id
+            );
+            assert!(scheduler.has_played(&m.user2_id, &m.user1_id));
+        }
+    }
+    // Four players have exactly six pairings, i.e. three full rounds.
+    assert_eq!(seen.len(), 6);
+    assert_eq!(scheduler.rounds_played(), 3);
+    assert!(scheduler.next_round(&users).is_empty());
+}
+
+#[test]
+fn percentile_labels_place_the_top_match_at_100() {
+    let users = vec![
+        user("a", &[1, 1, 1]),
+        user("b", &[7, 7, 7]),
+        user("c", &[3, 4, 4]),
+        user("d", &[4, 4, 4]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let scores: Vec<f64> = matcher
+        .calculate_all_pairs(&users)
+        .into_iter()
+        .map(|(_, _, s)| s)
+        .collect();
+    let mut matches = matcher.find_matches(&users).unwrap();
+    label_matches(&mut matches, &scores);
+
+    // a-b (18) tops the pool; c-d (1) is the least opposed of all six pairs.
+    assert_eq!(matches[0].percentile, Some(100.0));
+    assert!((matches[1].percentile.unwrap() - 100.0 / 6.0).abs() < 1e-12);
+
+    let mut unlabelled = matcher.find_matches(&users).unwrap();
+    label_matches(&mut unlabelled, &[]);
+    assert!(unlabelled.iter().all(|m| m.percentile.is_none()));
+}
+
+#[test]
+fn balance_penalty_prefers_mutually_passionate_pairs() {
+    let users = vec![
+        user("passionate", &[1, 1, 1, 1]),
+        user("indifferent", &[5, 5, 4, 4]),
+        user("rival", &[7, 7, 1, 1]),
+    ];
+    // Raw scores: passionate-indifferent 14, passionate-rival 12.
+    let plain = NemesisMatcher::new(SimpleDifferenceScorer);
+    assert!(plain.find_matches(&users).unwrap()[0].involves("indifferent"));
+
+    let balanced = NemesisMatcher::new(SimpleDifferenceScorer).with_balance_penalty(0.5);
+    let matches = balanced.find_matches(&users).unwrap();
+    assert_eq!(matches.len(), 1);
+    assert!(matches[0].involves("passionate") && matches[0].involves("rival"));
+    assert_eq!(matches[0].score, 12.0);
+}
+
+#[test]
+fn selection_options_leave_scores_and_stats_raw() {
+    let users = vec![
+        user("passionate", &[1, 1, 1, 1]),
+        user("indifferent", &[5, 5, 4, 4]),
+    ];
+    let balanced = NemesisMatcher::new(SimpleDifferenceScorer).with_balance_penalty(0.5);
+    assert_eq!(
+        balanced.find_matches(&users).unwrap(),
+        vec![Match::new("indifferent", "passionate", 14.0)]
+    );
+    assert_eq!(
+        balanced.find_matches_optimal(&users),
+        vec![Match::new("passionate", "indifferent", 14.0)]
+    );
+
+    let strict = balanced
+        .with_min_engagement(3.0)
+        .with_opposition_floor(100.0);
+    assert!(strict.find_matches(&users).unwrap().is_empty());
+    assert_eq!(strict.calculate_all_pairs(&users), vec![(0, 1, 14.0)]);
+    assert_eq!(strict.pool_diversity(&users), 14.0);
+    assert_eq!(strict.score_histogram(&users, 4), vec![(14.0, 14.0, 1)]);
+}
+
+#[test]
+fn swing_questions_finds_the_question_that_decides_the_matching() {
+    // Greedy pairs a-c (10) and b-d (7); without question 0 it pairs b-c and
+    // a-d instead, breaking both pairs. Questions 1 and 2 change nothing.
+    let users = vec![
+        user("a", &[1, 2, 2]),
+        user("b", &[3, 5, 2]),
+        user("c", &[6, 3, 6]),
+        user("d", &[6, 3, 4]),
+    ];
+    assert_eq!(
+        swing_questions(&users, &SimpleDifferenceScorer),
+        vec![(0, 2), (1, 0), (2, 0)]
+    );
+}
+
+#[test]
+fn min_engagement_excludes_neutral_users_before_pairing() {
+    let users = vec![
+        user("a", &[1, 1, 2]),
+        user("fence", &[4, 4, 4]),
+        user("b", &[7, 6, 7]),
+        user("c", &[2, 7, 3]),
+        user("d", &[6, 1, 5]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer).with_min_engagement(1.0);
+    let disengaged: Vec<&str> = matcher
+        .disengaged_users(&users)
+        .iter()
+        .map(|u| u.id.as_str())
+        .collect();
+    assert_eq!(disengaged, ["fence"]);
+
+    let matches = matcher.find_matches(&users).unwrap();
+    assert!(matches.iter().all(|m| !m.involves("fence")));
+    let engaged: Vec<User> = users.iter().filter(|u| u.id != "fence").cloned().collect();
+    assert_eq!(
+        matches,
+        NemesisMatcher::new(SimpleDifferenceScorer)
+            .find_matches(&engaged)
+            .unwrap()
+    );
+    assert_eq!(matches.len(), 2);
+}
+
+#[test]
+fn greedy_result_does_not_depend_on_input_order() {
+    // Plenty of tied scores, so index-based tie-breaking would show through.
+    let users = vec![
+        user("d", &[1, 4]),
+        user("a", &[7, 4]),
+        user("f", &[4, 1]),
+        user("c", &[4, 7]),
+        user("b", &[1, 1]),
+        user("e", &[7, 7]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let expected = matcher.find_matches(&users).unwrap();
+    let mut shuffled = users.clone();
+    for step in 0..6 {
+        shuffled.rotate_left(1);
+        shuffled.swap(step % 6, (step * 5 + 1) % 6);
+        assert_eq!(matcher.find_matches(&shuffled).unwrap(), expected);
+        let mut reversed = shuffled.clone();
+        reversed.reverse();
+        assert_eq!(matcher.find_matches(&reversed).unwrap(), expected);
+    }
+}
+
+/// Deliberately asymmetric: scoring (a, b) and (b, a) differ.
+struct Lopsided;
+
+impl ScoringStrategy for Lopsided {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        (user1.responses[0] - 2 * user2.responses[0]) as f64
+    }
+
+    fn name(&self) -> String {
+        "Lopsided".to_string()
+    }
+}
+
+#[test]
+fn asymmetric_scorers_are_scored_in_id_order() {
+    let users = vec![
+        user("c", &[2]),
+        user("a", &[7]),
+        user("b", &[1]),
+        user("d", &[4]),
+    ];
+    let matcher = NemesisMatcher::new(Lopsided);
+    let expected = matcher.find_matches(&users).unwrap();
+    let mut reversed = users.clone();
+    reversed.reverse();
+    assert_eq!(matcher.find_matches(&reversed).unwrap(), expected);
+    assert_eq!(
+        total(&matcher.find_matches_optimal(&reversed)),
+        total(&matcher.find_matches_optimal(&users))
+    );
+    // a–b scores 7 - 2 = 5 with "a" first, never 1 - 14.
+    assert!(matcher
+        .calculate_all_pairs(&reversed)
+        .contains(&(1, 2, 5.0)));
+
+    let backwards = [("b".to_string(), "a".to_string())];
+    assert_eq!(
+        matcher.score_pairs(&users, &backwards).unwrap()[0].score,
+        5.0
+    );
+    assert_eq!(
+        matcher.find_matches_with_fixed(&users, &backwards).unwrap()[0].score,
+        5.0
+    );
+    assert_eq!(
+        matcher.score_against_roster(&users[2..3], &users[1..2]),
+        [[5.0]]
+    );
+    let (left, right) = users.split_at(2);
+    assert_eq!(
+        total(&matcher.find_matches_bipartite(left, right)),
+        total(&matcher.find_matches_bipartite(right, left))
+    );
+}
+
+#[test]
+fn weighted_random_matching_is_reproducible_with_a_seed() {
+    let users: Vec<User> = (0..8)
+        .map(|i| {
+            user(
+                &format!("u{i}"),
+                &[1 + i % 7, 7 - (i * 3) % 7, 1 + (i * 2) % 7],
+            )
+        })
+        .collect();
+    let run = |seed| {
+        let mut matcher = WeightedRandomMatcher::new(SimpleDifferenceScorer).with_seed(seed);
+        (matcher.find_matches(&users), matcher.find_matches(&users))
+    };
+    let (first, second) = run(42);
+    assert_eq!(run(42), (first.clone(), second));
+    assert_eq!(first.len(), 4);
+}
+
+#[test]
+fn weighted_random_matching_favours_opposed_pairs() {
+    // a-b (12) is the only strongly opposed pair; a-c and b-d etc. score 6.
+    let users = vec![
+        user("a", &[1, 1]),
+        user("b", &[7, 7]),
+        user("c", &[4, 4]),
+        user("d", &[4, 4]),
+    ];
+    let mut matcher = WeightedRandomMatcher::new(SimpleDifferenceScorer)
+        .with_seed(7)
+        .with_temperature(3.0);
+    let trials = 2000;
+    let strong = (0..trials)
+        .filter(|_| {
+            matcher
+                .find_matches(&users)
+                .iter()
+                .any(|m| m.involves("a") && m.involves("b"))
+        })
+        .count();
+    // Uniform sampling would pick a-b first 1/6 of the time and a-b overall 1/3.
+    assert!(strong * 2 > trials, "a-b chosen {strong} of {trials} times");
+    assert!(strong < trials, "sampling should still vary");
+}
+
+#[test]
+fn minimal_subset_drops_questions_that_do_not_decide_the_top_match() {
+    // Questions 0 and 1 each make a-b the top pair on their own, so the
+    // first is redundant; question 2 barely varies and can go too.
+    let users = vec![
+        user("a", &[1, 1, 4]),
+        user("b", &[7, 6, 4]),
+        user("c", &[3, 3, 5]),
+        user("d", &[5, 4, 4]),
+    ];
+    let kept = minimal_question_subset(&users, &SimpleDifferenceScorer, 1);
+    assert_eq!(kept, vec![1]);
+    assert_eq!(
+        minimal_question_subset(&users, &SimpleDifferenceScorer, 0),
+        Vec::<usize>::new()
+    );
+}
+
+#[test]
+fn score_pairs_scores_only_the_requested_pairs() {
+    let users = vec![user("a", &[1, 1]), user("b", &[7, 7]), user("c", &[4, 2])];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let scored = matcher
+        .score_pairs(&users, &[fixed("c", "a"), fixed("a", "b")])
+        .unwrap();
+    assert_eq!(
+        scored,
+        vec![Match::new("c", "a", 4.0), Match::new("a", "b", 12.0)]
+    );
+
+    assert_eq!(
+        matcher.score_pairs(&users, &[fixed("a", "zed")]),
+        Err(NemesisError::UnknownUserId("zed".to_string()))
+    );
+}
+
+#[test]
+fn opposition_floor_leaves_identical_users_unpaired() {
+    let users = vec![
+        user("t1", &[4, 5]),
+        user("t2", &[4, 5]),
+        user("t3", &[4, 5]),
+        user("x", &[1, 1]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer).with_opposition_floor(0.0);
+    let matches = matcher.find_matches(&users).unwrap();
+    assert_eq!(matches.len(), 1);
+    assert!(matches[0].involves("x"));
+    assert_eq!(unmatched_users(&users, &matches).len(), 2);
+
+    // Without the floor the two remaining twins get paired for 0.
+    let plain = NemesisMatcher::new(SimpleDifferenceScorer)
+        .find_matches(&users)
+        .unwrap();
+    assert_eq!(plain.len(), 2);
+    assert_eq!(plain[1].score, 0.0);
+}
+
+#[test]
+fn checked_greedy_select_rejects_out_of_range_indices() {
+    let users = vec![user("a", &[1]), user("b", &[7])];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    assert_eq!(
+        matcher.try_greedy_select(&users, vec![(0, 1, 6.0), (1, 5, 3.0)]),
+        Err(NemesisError::PairIndexOutOfBounds { index: 5, len: 2 })
+    );
+    assert_eq!(
+        matcher
+            .try_greedy_select(&users, vec![(0, 1, 6.0)])
+            .unwrap(),
+        vec![Match::new("a", "b", 6.0)]
+    );
+}
+
+#[test]
+fn match_indices_agree_with_find_matches() {
+    let users = random_users(11, 6, 5);
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let by_index: Vec<(String, String, f64)> = matcher
+        .find_match_indices(&users)
+        .into_iter()
+        .map(|(i, j, score)| (users[i].id.clone(), users[j].id.clone(), score))
+        .collect();
+    let by_id: Vec<(String, String, f64)> = matcher
+        .find_matches(&users)
+        .unwrap()
+        .into_iter()
+        .map(|m| (m.user1_id, m.user2_id, m.score))
+        .collect();
+    assert_eq!(by_index.len(), 5);
+    assert_eq!(by_index, by_id);
+}
+
+#[test]
+fn rounded_matches_euclidean_scores() {
+    let users = vec![user("a", &[1, 2, 4]), user("b", &[2, 4, 7])];
+    let m = NemesisMatcher::new(EuclideanDistanceScorer)
+        .find_matches(&users)
+        .unwrap()[0]
+        .clone();
+    // sqrt(1 + 4 + 9) = 3.7416...
+    assert_eq!(m.rounded(0).score, 4.0);
+    assert_eq!(m.rounded(1).score, 3.7);
+    assert_eq!(m.rounded(2).score, 3.74);
+    assert_eq!(m.rounded(2).user1_id, "a");
+    assert_eq!(Match::new("a", "b", 2.5).rounded(0).score, 3.0);
+}
+
+#[test]
+fn next_round_matches_the_previous_leftover_first() {
+    let mut matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let round1 = vec![user("a", &[1, 1]), user("b", &[7, 7]), user("c", &[4, 4])];
+    let matches = matcher.find_matches_next_round(&round1).unwrap();
+    assert_eq!(matches, vec![Match::new("a", "b", 12.0)]);
+    assert_eq!(matcher.last_leftovers(), ["c"]);
+
+    // Plain greedy would pair a–b and d–e, leaving c out again.
+    let round2 = vec![
+        user("a", &[1, 1]),
+        user("b", &[7, 7]),
+        user("c", &[4, 4]),
+        user("d", &[1, 2]),
+        user("e", &[7, 6]),
+    ];
+    assert_eq!(
+        unmatched_users(&round2, &matcher.find_matches(&round2).unwrap())[0].id,
+        "c"
+    );
+    let matches = matcher.find_matches_next_round(&round2).unwrap();
+    assert_eq!(matches[0], Match::new("a", "c", 6.0));
+    assert!(!unmatched_users(&round2, &matches)
+        .iter()
+        .any(|u| u.id == "c"));
+    assert_eq!(matcher.last_leftovers(), ["e"]);
+}
+
+#[test]
+fn next_round_carries_every_leftover() {
+    // With a floor, both identical pairs sit out rather than matching each other.
+    let mut matcher = NemesisMatcher::new(SimpleDifferenceScorer).with_opposition_floor(0.0);
+    let round1 = vec![
+        user("a", &[4, 4]),
+        user("b", &[4, 4]),
+        user("c", &[1, 1]),
+        user("d", &[7, 7]),
+    ];
+    assert_eq!(
+        matcher.find_matches_next_round(&round1).unwrap(),
+        vec![Match::new("c", "d", 12.0)]
+    );
+    assert_eq!(matcher.last_leftovers(), ["a", "b"]);
+
+    // Two users sharing the id "a" are each other's best partner, but a
+    // pair can't be forced onto a single id.
+    let round2 = vec![
+        user("a", &[1, 1]),
+        user("a", &[7, 7]),
+        user("b", &[4, 4]),
+        user("c", &[1, 1]),
+        user("d", &[7, 7]),
+    ];
+    let matches = matcher.find_matches_next_round(&round2).unwrap();
+    assert_eq!(
+        matches[..2],
+        [Match::new("a", "c", 12.0), Match::new("b", "d", 6.0)]
+    );
+}
+
+#[test]
+fn match_builder_combines_scorer_threshold_exclusions_and_objective() {
+    let users = vec![
+        user("a", &[1, 1]),
+        user("b", &[7, 7]),
+        user("c", &[2, 2]),
+        user("d", &[6, 6]),
+        user("e", &[4, 4]),
+        user("f", &[4, 5]),
+    ];
+    let matcher = MatchBuilder::new()
+        .scorer(EuclideanDistanceScorer)
+        .exclude("a", "b")
+        .exclude("c", "d")
+        .threshold(1.5)
+        .objective(Objective::MaxTotal)
+        .build();
+    assert_eq!(matcher.objective(), Objective::MaxTotal);
+
+    let matches = matcher.find_matches(&users);
+    let pairs: HashSet<_> = matches
+        .iter()
+        .map(|m| pair_key(&m.user1_id, &m.user2_id))
+        .collect();
+    // a–b and c–d are excluded and e–f (score 1) is below the threshold, so
+    // the best full matching is a–d, b–e, c–f.
+    assert_eq!(
+        pairs,
+        HashSet::from([pair_key("a", "d"), pair_key("b", "e"), pair_key("c", "f")])
+    );
+}
+
+#[test]
+fn requests_honor_mutual_then_earlier_one_sided() {
+    let users = vec![
+        user("a", &[1, 1]),
+        user("b", &[2, 2]),
+        user("c", &[7, 7]),
+        user("d", &[6, 6]),
+        user("e", &[4, 4]),
+        user("f", &[1, 7]),
+    ];
+    let request = |a: &str, b: &str| (a.to_string(), b.to_string());
+    let requests = vec![
+        // One-sided, listed first, but a and b asked for each other.
+        request("c", "a"),
+        request("a", "b"),
+        request("b", "a"),
+        // c and e both want d; c asked first.
+        request("c", "d"),
+        request("e", "d"),
+        request("ghost", "f"),
+    ];
+    let matches =
+        NemesisMatcher::new(SimpleDifferenceScorer).find_matches_with_requests(&users, &requests);
+    assert_eq!(
+        matches,
+        vec![
+            Match::new("a", "b", 2.0),
+            Match::new("c", "d", 2.0),
+            Match::new("e", "f", 6.0),
+        ]
+    );
+}
+
+#[test]
+fn counts_perfect_matchings_of_four() {
+    // {01,23}, {02,13}, {03,12}.
+    assert_eq!(count_perfect_matchings(4, &HashSet::new()).unwrap(), 3);
+    assert_eq!(
+        count_perfect_matchings(4, &HashSet::from([(1, 0)])).unwrap(),
+        2
+    );
+    // Excluding everything user 0 could pair with leaves nothing.
+    let isolated = HashSet::from([(0, 1), (0, 2), (3, 0)]);
+    assert_eq!(count_perfect_matchings(4, &isolated).unwrap(), 0);
+    assert_eq!(count_perfect_matchings(5, &HashSet::new()).unwrap(), 0);
+    assert_eq!(
+        count_perfect_matchings(12, &HashSet::new()).unwrap(),
+        10_395
+    );
+    assert_eq!(
+        count_perfect_matchings(MAX_COUNTED_USERS + 2, &HashSet::new()),
+        Err(NemesisError::PoolTooLarge {
+            got: MAX_COUNTED_USERS + 2,
+            max: MAX_COUNTED_USERS
+        })
+    );
+}
+
+#[test]
+fn cross_pool_matching_avoids_previous_pairs() {
+    let club_a = vec![user("a1", &[1, 1]), user("a2", &[4, 4])];
+    let club_b = vec![user("b1", &[7, 7]), user("b2", &[5, 5])];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    assert_eq!(
+        matcher.match_cross_pool_avoiding(&club_a, &club_b, &HashSet::new()),
+        matcher.find_matches_bipartite(&club_a, &club_b)
+    );
+
+    // a1–b1 met last time (recorded in the other order), so a1 gets b2.
+    let history = HashSet::from([("b1".to_string(), "a1".to_string())]);
+    let matches = matcher.match_cross_pool_avoiding(&club_a, &club_b, &history);
+    assert_eq!(
+        matches,
+        vec![Match::new("a1", "b2", 8.0), Match::new("a2", "b1", 6.0)]
+    );
+}
+
+#[test]
+fn extremes_for_finds_nemesis_and_twin() {
+    let target = user("me", &[1, 2, 7]);
+    let pool = vec![
+        user("mild", &[4, 4, 4]),
+        target.clone(),
+        user("nemesis", &[7, 6, 1]),
+        user("twin", &[1, 3, 7]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let (nemesis, twin) = matcher.extremes_for(&target, &pool);
+    assert_eq!(nemesis, Some(Match::new("me", "nemesis", 16.0)));
+    assert_eq!(twin, Some(Match::new("me", "twin", 1.0)));
+    assert_eq!(nemesis, matcher.best_opponent(&target, &pool));
+
+    // Everyone scores alike: still two different people.
+    let flat = vec![user("x", &[4, 4, 4]), user("y", &[4, 4, 4])];
+    let (nemesis, twin) = matcher.extremes_for(&user("me", &[1, 4, 4]), &flat);
+    assert_ne!(nemesis.unwrap().user2_id, twin.unwrap().user2_id);
+    assert_eq!(matcher.extremes_for(&target, &[]), (None, None));
+}
+
+#[test]
+fn leftover_policies_drop_different_users() {
+    let users = vec![
+        user("a", &[1, 1]),
+        user("quiet", &[4, 5]),
+        user("late", &[7, 7]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    assert_eq!(DropLowestEngagement.choose_leftover(&users), 1);
+    assert_eq!(DropLast.choose_leftover(&users), 2);
+
+    assert_eq!(
+        matcher.find_matches_with_leftover(&users, &DropLowestEngagement),
+        vec![Match::new("a", "late", 12.0)]
+    );
+    let policy: &dyn LeftoverPolicy = &DropLast;
+    assert_eq!(
+        matcher.find_matches_with_leftover(&users, policy),
+        vec![Match::new("a", "quiet", 7.0)]
+    );
+}
+
+#[test]
+fn roster_grid_has_one_row_per_newcomer() {
+    let newcomers = vec![user("n1", &[1, 1]), user("n2", &[4, 6])];
+    let roster = vec![
+        user("h1", &[7, 7]),
+        user("h2", &[1, 2]),
+        user("h3", &[4, 4]),
+    ];
+    let grid =
+        NemesisMatcher::new(SimpleDifferenceScorer).score_against_roster(&newcomers, &roster);
+    assert_eq!(grid.len(), 2);
+    assert!(grid.iter().all(|row| row.len() == 3));
+    assert_eq!(grid[0][0], 12.0);
+    assert_eq!(grid[0][1], 1.0);
+    assert_eq!(grid[1][2], 2.0);
+}
+
+#[test]
+fn validate_matching_accepts_a_perfect_matching() {
+    let users = vec![
+        user("a", &[1]),
+        user("b", &[7]),
+        user("c", &[4]),
+        user("d", &[2]),
+        user("e", &[6]),
+    ];
+    let matches = NemesisMatcher::new(SimpleDifferenceScorer)
+        .find_matches(&users)
+        .unwrap();
+    assert_eq!(validate_matching(&matches, &users), Ok(()));
+}
+
+#[test]
+fn validate_matching_reports_the_violation() {
+    let users = vec![
+        user("a", &[1]),
+        user("b", &[7]),
+        user("c", &[4]),
+        user("d", &[2]),
+    ];
+    let duplicated = vec![Match::new("a", "b", 6.0), Match::new("c", "a", 3.0)];
+    assert_eq!(
+        validate_matching(&duplicated, &users),
+        Err(NemesisError::DuplicateUserId("a".to_string()))
+    );
+    assert_eq!(
+        validate_matching(&[Match::new("a", "zed", 1.0)], &users),
+        Err(NemesisError::UnknownUserId("zed".to_string()))
+    );
+    assert_eq!(
+        validate_matching(&[Match::new("a", "b", 6.0)], &users),
+        Err(NemesisError::IncompleteMatching {
+            unmatched: vec!["c".to_string(), "d".to_string()]
+        })
+    );
+}
+
+#[test]
+fn match_blocks_pairs_opposed_teams() {
+    let blocks = vec![
+        vec![user("l1", &[1, 1]), user("l2", &[2, 1])],
+        vec![user("m1", &[4, 4]), user("m2", &[4, 3])],
+        vec![user("r1", &[7, 7]), user("r2", &[6, 7])],
+        vec![user("x1", &[1, 7]), user("x2", &[7, 1])],
+    ];
+    let pairs = match_blocks(&blocks, &SimpleDifferenceScorer);
+    assert_eq!(pairs.len(), 2);
+    // Left vs right: gaps 12, 11, 11, 10 average to 11.
+    assert_eq!(pairs[0], (0, 2, 11.0));
+    // The centrists meet the mixed block: gaps 6, 6, 5, 7 average to 6.
+    assert_eq!(pairs[1], (1, 3, 6.0));
+}
+
+#[test]
+fn top_pairs_ranks_overlapping_pairs() {
+    let users = vec![
+        user("a", &[1, 1]),
+        user("b", &[7, 7]),
+        user("c", &[7, 6]),
+        user("d", &[4, 4]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    // a appears in all three: a leaderboard, not a matching.
+    assert_eq!(
+        matcher.top_pairs(&users, 3),
+        vec![
+            Match::new("a", "b", 12.0),
+            Match::new("a", "c", 11.0),
+            Match::new("a", "d", 6.0),
+        ]
+    );
+    assert_eq!(matcher.top_pairs(&users, 10).len(), 6);
+    assert!(matcher.top_pairs(&users, 0).is_empty());
+}
+
+#[test]
+fn top_pairs_ranks_like_greedy_selection() {
+    let users = vec![
+        user("d", &[1, 1]),
+        user("c", &[7, 7]),
+        user("b", &[1, 1]),
+        user("a", &[7, 7]),
+        user("twin", &[7, 7]),
+        user("fence", &[4, 4]),
+    ];
+    // Four pairs tie at 12; ids decide, whatever the input order.
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    assert_eq!(
+        matcher.top_pairs(&users, 2),
+        vec![Match::new("a", "b", 12.0), Match::new("a", "d", 12.0)]
+    );
+
+    // Neither the disengaged user nor the zero-opposition twins are listed.
+    let strict = matcher.with_min_engagement(1.0).with_opposition_floor(0.0);
+    let listed = strict.top_pairs(&users, 20);
+    assert_eq!(listed.len(), 6);
+    assert!(listed.iter().all(|m| !m.involves("fence") && m.score > 0.0));
+}
+
+#[test]
+fn clash_limit_rejects_the_most_heated_pair() {
+    let users = vec![
+        user("a", &[1, 1, 1, 4]),
+        user("b", &[7, 7, 7, 4]),
+        user("c", &[6, 5, 2, 4]),
+        user("d", &[2, 3, 6, 4]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    // Unconstrained, a–b (three 6-point clashes) goes first.
+    assert_eq!(
+        matcher.find_matches(&users).unwrap()[0],
+        Match::new("a", "b", 18.0)
+    );
+
+    let civil = Constraints::new().max_strong_clashes(5, 1);
+    assert_eq!(
+        matcher.find_matches_constrained(&users, &civil),
+        vec![Match::new("a", "c", 10.0), Match::new("b", "d", 10.0)]
+    );
+
+    // Responses are public, so off-scale answers can still reach a check.
+    let mut far = user("far", &[1, 4]);
+    far.responses[0] = i32::MIN;
+    let mut near = user("near", &[7, 4]);
+    near.responses[0] = i32::MAX;
+    assert!(!Constraints::new()
+        .max_strong_clashes(5, 0)
+        .allows(&far, &near, 0.0));
+    // A non-positive threshold counts even identical answers.
+    let strict = Constraints::new().max_strong_clashes(0, 1);
+    assert!(!strict.allows(&near, &near, 0.0));
+}
+
+#[test]
+fn greedy_cost_estimate_grows_quadratically() {
+    let small = estimate_cost(1_000, Objective::Greedy);
+    let large = estimate_cost(2_000, Objective::Greedy);
+    assert_eq!(small.pairs, 499_500);
+    assert_eq!(large.pairs, 1_999_000);
+    let ratio = large.operations / small.operations;
+    assert!((4.0..4.5).contains(&ratio), "ratio {ratio}");
+
+    let optimal = estimate_cost(2_000, Objective::MaxTotal);
+    assert_eq!(optimal.operations, 8e9);
+    assert!(optimal.time_class > large.time_class);
+    assert_eq!(
+        estimate_cost(10, Objective::Greedy).time_class,
+        TimeClass::Instant
+    );
+}
+
+#[test]
+fn optimal_constrained_matching_is_best_feasible() {
+    let users = vec![
+        user("a", &[1, 1]),
+        user("b", &[7, 7]),
+        user("c", &[2, 3]),
+        user("d", &[6, 5]),
+    ];
+    let matcher = NemesisMatcher::new(SimpleDifferenceScorer);
+    let pairs_of = |matches: &[Match]| -> HashSet<(String, String)> {
+        matches
+            .iter()
+            .map(|m| pair_key(&m.user1_id, &m.user2_id))
+            .collect()
+    };
+    let unconstrained = matcher.find_matches_optimal(&users);
+    assert!(pairs_of(&unconstrained).contains(&pair_key("a", "b")));
+
+    // Without a–b, the choice is a–d + b–c (18) or a–c + b–d (6).
+    let constraints = Constraints::new().exclude("a", "b");
+    let (matches, feasibility) = matcher.find_matches_optimal_constrained(&users, &constraints);
+    assert_eq!(feasibility, Feasibility::Feasible);
+    assert_eq!(
+        pairs_of(&matches),
+        HashSet::from([pair_key("a", "d"), pair_key("b", "c")])
+    );
+    assert_eq!(total(&matches), 18.0);
+
+    // Nobody may face a: the best of the rest, with a reported.
+    let isolating = constraints.exclude("a", "c").exclude("a", "d");
+    let (matches, feasibility) = matcher.find_matches_optimal_constrained(&users, &isolating);
+    assert_eq!(
+        feasibility,
+        Feasibility::Infeasible {
+            unplaceable: vec!["a".to_string(), "d".to_string()]
+        }
+    );
+    assert_eq!(matches, vec![Match::new("b", "c", 9.0)]);
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use std::collections::{HashMap, HashSet};
+
+use common::{pair_key, user};
+use nemesis_finder::matcher::MaximinMatcher;
+use nemesis_finder::{Match, NemesisMatcher, ScoringStrategy, SimpleDifferenceScorer, User};
+
+/// Scores pairs from a lookup table keyed by id, for hand-built instances.
+struct TableScorer(HashMap<(String, String), f64>);
+
+impl TableScorer {
+    fn new(entries: &[(&str, &str, f64)]) -> Self {
+        TableScorer(
+            entries
+                .iter()
+                .map(|&(a, b, w)| (pair_key(a, b), w))
+                .collect(),
+        )
+    }
+}
+
+impl ScoringStrategy for TableScorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        self.0
+            .get(&pair_key(&user1.id, &user2.id))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    fn name(&self) -> String {
+        "Table".to_string()
+    }
+}
+
+fn pairs(matches: &[Match]) -> HashSet<(String, String)> {
+    matches
+        .iter()
+        .map(|m| pair_key(&m.user1_id, &m.user2_id))
+        .collect()
+}
+
+fn four_users() -> Vec<User> {
+    ["a", "b", "c", "d"]
+        .iter()
+        .map(|id| user(id, &[4]))
+        .collect()
+}
+
+#[test]
+fn maximin_differs_from_max_total() {
+    let scorer = TableScorer::new(&[
+        ("a", "b", 10.0),
+        ("c", "d", 1.0),
+        ("a", "c", 5.0),
+        ("b", "d", 5.0),
+        ("a", "d", 0.0),
+        ("b", "c", 0.0),
+    ]);
+    let users = four_users();
+
+    let max_total = NemesisMatcher::new(&scorer).find_matches_optimal(&users);
+    assert_eq!(
+        pairs(&max_total),
+        HashSet::from([pair_key("a", "b"), pair_key("c", "d")])
+    );
+
+    let maximin = MaximinMatcher::new(&scorer).find_matches(&users);
+    assert_eq!(
+        pairs(&maximin),
+        HashSet::from([pair_key("a", "c"), pair_key("b", "d")])
+    );
+    let weakest = maximin
+        .iter()
+        .map(|m| m.score)
+        .fold(f64::INFINITY, f64::min);
+    assert_eq!(weakest, 5.0);
+}
+
+#[test]
+fn maximin_covers_odd_pools_and_trivial_inputs() {
+    let users = vec![
+        user("a", &[1]),
+        user("b", &[7]),
+        user("c", &[2]),
+        user("d", &[6]),
+        user("e", &[4]),
+    ];
+    let matches = MaximinMatcher::new(SimpleDifferenceScorer).find_matches(&users);
+    assert_eq!(matches.len(), 2);
+    assert!(MaximinMatcher::new(SimpleDifferenceScorer)
+        .find_matches(&[])
+        .is_empty());
+    assert!(MaximinMatcher::new(SimpleDifferenceScorer)
+        .find_matches(&users[..1])
+        .is_empty());
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::user;
+use nemesis_finder::packed::PackedUser;
+use nemesis_finder::{NemesisError, User};
+
+#[test]
+fn packed_user_round_trips_25_responses() {
+    let responses: Vec<i32> = (0..25).map(|i| 1 + (i * 3) % 7).collect();
+    let original = user("long", &responses).with_tag("region", "north");
+    let packed = PackedUser::pack(&original).unwrap();
+    assert_eq!(packed.responses.len(), 13);
+
+    let json = serde_json::to_string(&packed).unwrap();
+    let restored: PackedUser = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.unpack().unwrap(), original);
+}
+
+#[test]
+fn unpacking_rejects_corrupt_data() {
+    let packed = PackedUser::pack(&user("a", &[1, 7, 4])).unwrap();
+
+    let mut bad_char = packed.clone();
+    bad_char.responses.replace_range(0..1, "*");
+    assert!(matches!(
+        bad_char.unpack(),
+        Err(NemesisError::Deserialization(_))
+    ));
+
+    // "A" decodes to two zeros: an off-scale first answer.
+    let mut zero = packed.clone();
+    zero.responses = "A".to_string();
+    assert_eq!(
+        zero.unpack(),
+        Err(NemesisError::InvalidResponse { index: 0, value: 0 })
+    );
+
+    // "I" is (1, 0): padding is only allowed in the last character.
+    let mut early_padding = packed;
+    early_padding.responses = "IJ".to_string();
+    assert!(matches!(
+        early_padding.unpack(),
+        Err(NemesisError::Deserialization(_))
+    ));
+}
+
+#[test]
+fn packed_conversions_round_trip() {
+    let original = user("a", &[3, 1, 7, 4, 2]);
+    let packed = PackedUser::try_from(&original).unwrap();
+    assert_eq!(User::try_from(&packed).unwrap(), original);
+
+    let corrupt = PackedUser {
+        responses: "A".to_string(),
+        ..packed
+    };
+    assert!(User::try_from(&corrupt).is_err());
+}
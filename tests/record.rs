@@ -0,0 +1,40 @@
+mod common;
+
+use common::user;
+use nemesis_finder::evaluation::total_score;
+use nemesis_finder::record::{run_and_record, MatchingRun};
+use nemesis_finder::{NemesisError, StrategyConfig};
+
+#[test]
+fn recorded_run_round_trips_through_json() {
+    let users = vec![
+        user("a", &[1, 1, 4]),
+        user("b", &[7, 7, 4]),
+        user("c", &[4, 4, 1]),
+        user("d", &[4, 4, 7]),
+    ];
+    let config = StrategyConfig::Weighted {
+        weights: vec![1.0, 2.0, 0.5],
+    };
+    let run = run_and_record(&users, &config).unwrap();
+    assert_eq!(run.user_ids, ["a", "b", "c", "d"]);
+    assert_eq!(run.matches.len(), 2);
+
+    let json = serde_json::to_string(&run).unwrap();
+    let restored: MatchingRun = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, run);
+    assert_eq!(total_score(&restored.matches), restored.total_score);
+    assert_eq!(
+        run_and_record(&users, &restored.config).unwrap().matches,
+        restored.matches
+    );
+}
+
+#[test]
+fn invalid_config_is_rejected() {
+    let config = StrategyConfig::Weighted { weights: vec![] };
+    assert!(matches!(
+        run_and_record(&[user("a", &[1])], &config),
+        Err(NemesisError::InvalidWeights(_))
+    ));
+}
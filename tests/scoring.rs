@@ -0,0 +1,1042 @@
+mod common;
+
+use std::cell::Cell;
+
+use common::user;
+use nemesis_finder::scoring::{
+    fit_weights, ideal_opponent_responses, marginal_contribution, pool_nemesis_profile,
+    score_breakdown, self_mirror_opposition, ConfidenceCombine,
+};
+use nemesis_finder::testing::random_users;
+use nemesis_finder::user::{impute_missing, Imputation, PartialUser};
+use nemesis_finder::{
+    CachedScorer, CapPerQuestion, CircularDifferenceScorer, ClampScorer, ConfidenceScorer,
+    CosineSimilarityScorer, EuclideanDistanceScorer, IgnoreMutualNeutral, ImportanceScorer,
+    InvertScorer, NemesisError, NemesisMatcher, PolarizationScorer, RecordingScorer, Scale, Scorer,
+    ScoringStrategy, SharedConvictionScorer, SimpleDifferenceScorer, StrategyConfig,
+    TableConvictionScorer, User, WeightedCosineScorer, WeightedScorer, ZScoreScorer,
+};
+
+#[test]
+fn simple_difference_sums_absolute_gaps() {
+    let a = user("a", &[1, 4, 7]);
+    let b = user("b", &[7, 4, 1]);
+    assert_eq!(SimpleDifferenceScorer.calculate_score(&a, &b), 12.0);
+    assert_eq!(SimpleDifferenceScorer.calculate_score(&a, &a), 0.0);
+}
+
+#[test]
+fn euclidean_is_root_of_squared_gaps() {
+    let a = user("a", &[1, 1]);
+    let b = user("b", &[4, 5]);
+    assert_eq!(EuclideanDistanceScorer.calculate_score(&a, &b), 5.0);
+}
+
+#[test]
+fn weighted_scales_each_question() {
+    let scorer = WeightedScorer::new(vec![2.0, 0.5]).unwrap();
+    let a = user("a", &[1, 1]);
+    let b = user("b", &[3, 5]);
+    assert_eq!(scorer.calculate_score(&a, &b), 6.0);
+}
+
+#[test]
+fn weighted_rejects_bad_weights() {
+    assert!(matches!(
+        WeightedScorer::new(vec![]),
+        Err(NemesisError::InvalidWeights(_))
+    ));
+    assert!(matches!(
+        WeightedScorer::new(vec![1.0, -1.0]),
+        Err(NemesisError::InvalidWeights(_))
+    ));
+    assert!(matches!(
+        WeightedScorer::new(vec![f64::NAN]),
+        Err(NemesisError::InvalidWeights(_))
+    ));
+}
+
+#[test]
+fn polarization_rewards_mutual_conviction() {
+    let scorer = PolarizationScorer::default();
+    let extremes = scorer.calculate_score(&user("a", &[1]), &user("b", &[7]));
+    let moderates = scorer.calculate_score(&user("a", &[3]), &user("b", &[5]));
+    assert_eq!(extremes, 6.0 * 2.0 * 2.0);
+    assert_eq!(moderates, 2.0);
+}
+
+#[test]
+fn cosine_opposite_directions_score_two() {
+    let a = user("a", &[1, 7]);
+    let b = user("b", &[7, 1]);
+    let neutral = user("n", &[4, 4]);
+    assert!((CosineSimilarityScorer.calculate_score(&a, &b) - 2.0).abs() < 1e-12);
+    assert!(CosineSimilarityScorer.calculate_score(&a, &a).abs() < 1e-12);
+    assert_eq!(CosineSimilarityScorer.calculate_score(&a, &neutral), 1.0);
+}
+
+#[test]
+fn user_new_validates_responses() {
+    assert_eq!(
+        User::new("x", vec![1, 8]),
+        Err(NemesisError::InvalidResponse { index: 1, value: 8 })
+    );
+    assert_eq!(User::new("x", vec![]), Err(NemesisError::EmptyResponses));
+}
+
+/// Mean absolute difference; sensitive to how many questions are counted.
+struct MeanDifference;
+
+impl ScoringStrategy for MeanDifference {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        SimpleDifferenceScorer.calculate_score(user1, user2) / user1.responses.len() as f64
+    }
+
+    fn name(&self) -> String {
+        "MeanDifference".to_string()
+    }
+}
+
+#[test]
+fn ignore_mutual_neutral_excludes_questions_from_the_denominator() {
+    let a = user("a", &[1, 4, 4, 7]);
+    let b = user("b", &[7, 4, 4, 1]);
+    let without_neutral = (user("a", &[1, 7]), user("b", &[7, 1]));
+
+    let wrapped = IgnoreMutualNeutral::new(MeanDifference).unwrap();
+    assert_eq!(MeanDifference.calculate_score(&a, &b), 3.0);
+    assert_eq!(wrapped.calculate_score(&a, &b), 6.0);
+    assert_eq!(
+        wrapped.calculate_score(&a, &b),
+        MeanDifference.calculate_score(&without_neutral.0, &without_neutral.1)
+    );
+}
+
+#[test]
+fn ignore_mutual_neutral_matches_base_without_neutral_questions() {
+    let a = user("a", &[1, 4, 6]);
+    let b = user("b", &[5, 2, 3]);
+    let wrapped = IgnoreMutualNeutral::new(MeanDifference).unwrap();
+    assert_eq!(
+        wrapped.calculate_score(&a, &b),
+        MeanDifference.calculate_score(&a, &b)
+    );
+    assert_eq!(
+        IgnoreMutualNeutral::new(SimpleDifferenceScorer)
+            .unwrap()
+            .calculate_score(&a, &b),
+        SimpleDifferenceScorer.calculate_score(&a, &b)
+    );
+}
+
+#[test]
+fn ignore_mutual_neutral_all_neutral_pair_scores_zero() {
+    let a = user("a", &[4, 4]);
+    let wrapped = IgnoreMutualNeutral::new(MeanDifference).unwrap();
+    assert_eq!(wrapped.calculate_score(&a, &a), 0.0);
+    assert_eq!(wrapped.name(), "IgnoreMutualNeutral(MeanDifference)");
+}
+
+#[test]
+fn ignore_mutual_neutral_keeps_per_question_data_aligned() {
+    let weighted = WeightedScorer::new(vec![1.0, 5.0]).unwrap();
+    assert!(matches!(
+        IgnoreMutualNeutral::new(weighted),
+        Err(NemesisError::PositionDependent(_))
+    ));
+    assert!(IgnoreMutualNeutral::new(
+        CircularDifferenceScorer::new(7).with_circular_questions(vec![true])
+    )
+    .is_err());
+
+    // Question 0 is mutually neutral; the half-confident answer is question 1.
+    let a = user("a", &[4, 1, 1])
+        .with_confidence(vec![1.0, 0.5, 1.0])
+        .unwrap();
+    let b = user("b", &[4, 7, 7]);
+    let scorer = ConfidenceScorer::new(ConfidenceCombine::Min);
+    let wrapped = IgnoreMutualNeutral::new(scorer).unwrap();
+    assert_eq!(
+        wrapped.calculate_score(&a, &b),
+        scorer.calculate_score(&a, &b)
+    );
+}
+
+#[test]
+fn weighted_cosine_heavy_question_dominates_the_angle() {
+    // Centred on 4 the users agree on question 0 and clash on question 1.
+    let a = user("a", &[7, 1]);
+    let b = user("b", &[7, 7]);
+
+    let even = WeightedCosineScorer::new(vec![1.0, 1.0], 4.0).unwrap();
+    let agree_heavy = WeightedCosineScorer::new(vec![10.0, 1.0], 4.0).unwrap();
+    let clash_heavy = WeightedCosineScorer::new(vec![1.0, 10.0], 4.0).unwrap();
+
+    assert!((even.calculate_score(&a, &b) - 1.0).abs() < 1e-12);
+    assert!(agree_heavy.calculate_score(&a, &b) < 0.05);
+    assert!(clash_heavy.calculate_score(&a, &b) > 1.95);
+}
+
+#[test]
+fn weighted_cosine_matches_plain_cosine_with_unit_weights() {
+    let a = user("a", &[1, 3, 6]);
+    let b = user("b", &[5, 7, 2]);
+    let scorer = WeightedCosineScorer::new(vec![1.0; 3], 4.0).unwrap();
+    let plain = CosineSimilarityScorer.calculate_score(&a, &b);
+    assert!((scorer.calculate_score(&a, &b) - plain).abs() < 1e-12);
+}
+
+#[test]
+fn weighted_cosine_neutral_shifts_the_centre() {
+    let a = user("a", &[2, 2]);
+    let b = user("b", &[3, 3]);
+    let centred_on_four = WeightedCosineScorer::new(vec![1.0, 1.0], 4.0).unwrap();
+    let centred_between = WeightedCosineScorer::new(vec![1.0, 1.0], 2.5).unwrap();
+    assert!(centred_on_four.calculate_score(&a, &b).abs() < 1e-12);
+    assert!((centred_between.calculate_score(&a, &b) - 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn weighted_cosine_reuses_weight_validation() {
+    assert!(matches!(
+        WeightedCosineScorer::new(vec![1.0, -2.0], 4.0),
+        Err(NemesisError::InvalidWeights(_))
+    ));
+    assert!(matches!(
+        WeightedCosineScorer::new(vec![1.0], f64::NAN),
+        Err(NemesisError::InvalidWeights(_))
+    ));
+}
+
+#[test]
+fn clamp_caps_scores_above_max() {
+    let a = user("a", &[1, 1, 1]);
+    let b = user("b", &[7, 7, 7]);
+    let clamped = ClampScorer::new(PolarizationScorer::default(), 50.0);
+    assert_eq!(PolarizationScorer::default().calculate_score(&a, &b), 72.0);
+    assert_eq!(clamped.calculate_score(&a, &b), 50.0);
+    assert_eq!(clamped.score_bounds(3).1, 50.0);
+}
+
+struct NanScorer;
+
+impl ScoringStrategy for NanScorer {
+    fn calculate_score(&self, _: &User, _: &User) -> f64 {
+        f64::NAN
+    }
+
+    fn name(&self) -> String {
+        "Nan".to_string()
+    }
+}
+
+#[test]
+fn clamp_leaves_bad_inner_scores_for_the_checked_path() {
+    let a = user("a", &[1, 1]);
+    let b = user("b", &[7, 7]);
+    let nan = ClampScorer::new(NanScorer, 50.0);
+    assert!(nan.calculate_score(&a, &b).is_nan());
+    assert!(matches!(
+        nan.try_calculate_score(&a, &b),
+        Err(NemesisError::NonFiniteScore { .. })
+    ));
+
+    let huge = WeightedScorer::new(vec![f64::MAX, f64::MAX]).unwrap();
+    let infinite = ClampScorer::new(huge, 50.0);
+    assert_eq!(infinite.calculate_score(&a, &b), 50.0);
+    assert!(matches!(
+        infinite.try_calculate_score(&a, &b),
+        Err(NemesisError::NonFiniteScore { .. })
+    ));
+}
+
+#[test]
+fn clamp_passes_through_scores_below_max() {
+    let a = user("a", &[3, 4]);
+    let b = user("b", &[5, 4]);
+    let clamped = ClampScorer::new(SimpleDifferenceScorer, 50.0);
+    assert_eq!(clamped.calculate_score(&a, &b), 2.0);
+    assert_eq!(clamped.name(), "SimpleDifference(max 50)");
+}
+
+#[test]
+fn normalized_per_question_equalizes_survey_lengths() {
+    let short = (user("a", &[1; 4]), user("b", &[7; 4]));
+    let long = (user("a", &[1; 25]), user("b", &[7; 25]));
+
+    let simple = SimpleDifferenceScorer;
+    assert_eq!(simple.normalized_per_question(&short.0, &short.1), 6.0);
+    assert_eq!(simple.normalized_per_question(&long.0, &long.1), 6.0);
+
+    let euclid = EuclideanDistanceScorer;
+    assert_eq!(euclid.calculate_score(&long.0, &long.1), 30.0);
+    assert!((euclid.normalized_per_question(&short.0, &short.1) - 6.0).abs() < 1e-12);
+    assert!((euclid.normalized_per_question(&long.0, &long.1) - 6.0).abs() < 1e-12);
+
+    let polar = PolarizationScorer::default();
+    assert_eq!(
+        polar.normalized_per_question(&short.0, &short.1),
+        polar.normalized_per_question(&long.0, &long.1)
+    );
+
+    let cosine = CosineSimilarityScorer;
+    assert_eq!(
+        cosine.normalized_per_question(&short.0, &short.1),
+        cosine.normalized_per_question(&long.0, &long.1)
+    );
+}
+
+#[test]
+fn weighted_normalizes_by_total_weight() {
+    let scorer = WeightedScorer::new(vec![3.0, 1.0]).unwrap();
+    let a = user("a", &[1, 1]);
+    let b = user("b", &[7, 7]);
+    assert_eq!(scorer.calculate_score(&a, &b), 24.0);
+    assert_eq!(scorer.normalized_per_question(&a, &b), 6.0);
+}
+
+#[test]
+fn strategy_config_round_trips_weighted() {
+    let config = StrategyConfig::Weighted {
+        weights: vec![2.0, 0.5],
+    };
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(json, r#"{"strategy":"Weighted","weights":[2.0,0.5]}"#);
+    let restored: StrategyConfig = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, config);
+
+    let scorer = restored.build().unwrap();
+    let expected = WeightedScorer::new(vec![2.0, 0.5]).unwrap();
+    let (a, b) = (user("a", &[1, 1]), user("b", &[3, 5]));
+    assert_eq!(scorer.name(), "Weighted");
+    assert_eq!(
+        scorer.calculate_score(&a, &b),
+        expected.calculate_score(&a, &b)
+    );
+}
+
+#[test]
+fn strategy_config_round_trips_polarization() {
+    let custom = PolarizationScorer::new(3.0, 2.0, 0.5).unwrap();
+    let config = StrategyConfig::from(&custom);
+    let restored: StrategyConfig =
+        serde_json::from_str(&serde_json::to_string(&config).unwrap()).unwrap();
+    assert_eq!(restored, config);
+
+    let scorer = restored.build().unwrap();
+    let (a, b) = (user("a", &[1, 3]), user("b", &[7, 5]));
+    assert_eq!(scorer.name(), "Polarization");
+    assert_eq!(
+        scorer.calculate_score(&a, &b),
+        custom.calculate_score(&a, &b)
+    );
+}
+
+#[test]
+fn strategy_config_build_validates_parameters() {
+    let bad: StrategyConfig =
+        serde_json::from_str(r#"{"strategy":"Weighted","weights":[-1.0]}"#).unwrap();
+    assert!(matches!(bad.build(), Err(NemesisError::InvalidWeights(_))));
+    let simple: StrategyConfig =
+        serde_json::from_str(r#"{"strategy":"SimpleDifference"}"#).unwrap();
+    assert_eq!(simple.build().unwrap().name(), "SimpleDifference");
+}
+
+/// Bypasses `User::new` to build answers far outside the 1-7 scale.
+fn wide_user(id: &str, value: i32, num_questions: usize) -> User {
+    User {
+        responses: vec![value; num_questions],
+        ..user(id, &[1])
+    }
+}
+
+#[test]
+fn scorers_do_not_overflow_on_wide_scales() {
+    // Each difference is 2e9, already past i32::MAX; squared it is 4e18, and
+    // four of those overflow i64 as well.
+    let low = wide_user("low", -1_000_000_000, 4);
+    let high = wide_user("high", 1_000_000_000, 4);
+
+    assert_eq!(SimpleDifferenceScorer.calculate_score(&low, &high), 8e9);
+    assert_eq!(EuclideanDistanceScorer.calculate_score(&low, &high), 4e9);
+    let weighted = WeightedScorer::new(vec![1.0; 4]).unwrap();
+    assert_eq!(weighted.calculate_score(&low, &high), 8e9);
+    let polarization = PolarizationScorer::default();
+    assert_eq!(polarization.calculate_score(&low, &high), 8e9);
+}
+
+#[test]
+fn euclidean_handles_extreme_i32_values() {
+    let low = wide_user("low", i32::MIN, 3);
+    let high = wide_user("high", i32::MAX, 3);
+    let expected = (u32::MAX as f64) * 3f64.sqrt();
+    let score = EuclideanDistanceScorer.calculate_score(&low, &high);
+    assert!((score - expected).abs() / expected < 1e-12);
+    assert_eq!(
+        SimpleDifferenceScorer.calculate_score(&low, &high),
+        3.0 * u32::MAX as f64
+    );
+}
+
+#[test]
+fn invert_flips_pair_ordering() {
+    let a = user("a", &[1, 1]);
+    let close = user("close", &[2, 1]);
+    let far = user("far", &[7, 7]);
+    let inner = SimpleDifferenceScorer;
+    assert!(inner.calculate_score(&a, &far) > inner.calculate_score(&a, &close));
+
+    let inverted = InvertScorer::new(inner, inner.score_bounds(2).1);
+    assert_eq!(inverted.calculate_score(&a, &far), 0.0);
+    assert_eq!(inverted.calculate_score(&a, &close), 11.0);
+    assert!(inverted.calculate_score(&a, &close) > inverted.calculate_score(&a, &far));
+    assert_eq!(inverted.score_bounds(2), (0.0, 12.0));
+    assert_eq!(inverted.name(), "Invert(SimpleDifference)");
+}
+
+#[test]
+fn fit_weights_recovers_true_weights() {
+    let truth = WeightedScorer::new(vec![2.0, 0.5, 1.0, 0.0]).unwrap();
+    let users = random_users(30, 4, 77);
+    let pairs: Vec<(User, User, f64)> = users
+        .windows(2)
+        .map(|w| {
+            let score = truth.calculate_score(&w[0], &w[1]);
+            (w[0].clone(), w[1].clone(), score)
+        })
+        .collect();
+
+    let fitted = fit_weights(&pairs, 4).unwrap();
+    for (got, want) in fitted.weights().iter().zip(truth.weights()) {
+        assert!((got - want).abs() < 1e-6, "{:?}", fitted.weights());
+    }
+}
+
+#[test]
+fn fit_weights_rejects_bad_training_data() {
+    assert!(matches!(
+        fit_weights(&[], 3),
+        Err(NemesisError::InvalidWeights(_))
+    ));
+    let pairs = vec![(user("a", &[1, 2]), user("b", &[3]), 1.0)];
+    assert_eq!(
+        fit_weights(&pairs, 2),
+        Err(NemesisError::MismatchedLengths {
+            expected: 2,
+            got: 1
+        })
+    );
+}
+
+#[test]
+fn marginals_sum_to_the_full_simple_difference_score() {
+    let a = user("a", &[1, 5, 4, 7, 2]);
+    let b = user("b", &[7, 3, 4, 1, 6]);
+    let scorer = SimpleDifferenceScorer;
+    let total: f64 = (0..5)
+        .map(|q| marginal_contribution(&scorer, &a, &b, q).unwrap())
+        .sum();
+    assert_eq!(total, scorer.calculate_score(&a, &b));
+    assert_eq!(
+        score_breakdown(&scorer, &a, &b).unwrap(),
+        vec![6.0, 2.0, 0.0, 6.0, 4.0]
+    );
+    assert_eq!(marginal_contribution(&scorer, &a, &b, 5), Ok(0.0));
+    assert!(matches!(
+        marginal_contribution(&EuclideanDistanceScorer, &a, &b, 0),
+        Err(NemesisError::NotDecomposable(_))
+    ));
+    // A one-step sensitivity is still defined: the first gap shrinks 6 -> 5.
+    let euclidean = EuclideanDistanceScorer
+        .answer_sensitivity(&a, &b, 0)
+        .unwrap();
+    assert!((euclidean - (9.0 - 92f64.sqrt())).abs() < 1e-12);
+
+    let mut top = a.clone();
+    top.responses[0] = i32::MAX;
+    assert_eq!(
+        EuclideanDistanceScorer.answer_sensitivity(&top, &b, 0),
+        Ok(0.0)
+    );
+}
+
+#[test]
+fn weighted_marginals_respect_question_weights() {
+    let a = user("a", &[1, 1, 1]);
+    let b = user("b", &[3, 3, 3]);
+    let scorer = WeightedScorer::new(vec![1.0, 0.0, 2.5]).unwrap();
+    assert_eq!(
+        score_breakdown(&scorer, &a, &b).unwrap(),
+        vec![2.0, 0.0, 5.0]
+    );
+}
+
+#[test]
+fn low_confidence_questions_count_for_less() {
+    let a = user("a", &[1, 1]).with_confidence(vec![1.0, 0.2]).unwrap();
+    let b = user("b", &[7, 7]).with_confidence(vec![0.9, 0.5]).unwrap();
+    let min = ConfidenceScorer::default();
+    let product = ConfidenceScorer::new(ConfidenceCombine::Product);
+    // The sure question contributes 6·0.9, the unsure one only 6·0.2.
+    assert!((min.calculate_score(&a, &b) - (5.4 + 1.2)).abs() < 1e-9);
+    assert!((product.calculate_score(&a, &b) - (5.4 + 0.6)).abs() < 1e-9);
+}
+
+#[test]
+fn missing_confidence_means_full_certainty() {
+    let a = user("a", &[1, 4, 6]);
+    let b = user("b", &[7, 2, 6]);
+    assert_eq!(
+        ConfidenceScorer::default().calculate_score(&a, &b),
+        SimpleDifferenceScorer.calculate_score(&a, &b)
+    );
+}
+
+#[test]
+fn confidence_must_be_a_fraction_per_answer() {
+    assert_eq!(
+        user("a", &[1, 2]).with_confidence(vec![0.5, 1.5]),
+        Err(NemesisError::InvalidConfidence {
+            index: 1,
+            value: 1.5
+        })
+    );
+    assert_eq!(
+        user("a", &[1, 2]).with_confidence(vec![0.5]),
+        Err(NemesisError::MismatchedLengths {
+            expected: 2,
+            got: 1
+        })
+    );
+}
+
+/// Simple difference that counts how often it is asked for a score.
+#[derive(Default)]
+struct CountingScorer {
+    calls: Cell<usize>,
+}
+
+impl ScoringStrategy for CountingScorer {
+    fn calculate_score(&self, user1: &User, user2: &User) -> f64 {
+        self.calls.set(self.calls.get() + 1);
+        SimpleDifferenceScorer.calculate_score(user1, user2)
+    }
+
+    fn name(&self) -> String {
+        "Counting".to_string()
+    }
+}
+
+#[test]
+fn cached_scorer_scores_each_pair_once_across_runs() {
+    let users = random_users(6, 5, 3);
+    let counting = CountingScorer::default();
+    let cached = CachedScorer::new(&counting);
+    let matcher = NemesisMatcher::new(&cached);
+
+    let first = matcher.find_matches(&users).unwrap();
+    let second = matcher.find_matches(&users).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(counting.calls.get(), 15);
+    assert_eq!(cached.cached_pairs(), 15);
+    assert_eq!(
+        first,
+        NemesisMatcher::new(SimpleDifferenceScorer)
+            .find_matches(&users)
+            .unwrap()
+    );
+
+    cached.clear();
+    matcher.find_matches(&users).unwrap();
+    assert_eq!(counting.calls.get(), 30);
+}
+
+#[test]
+fn simple_difference_sensitivity_is_the_gap_direction() {
+    let a = user("a", &[5, 2, 4]);
+    let b = user("b", &[3, 6, 4]);
+    let scorer = SimpleDifferenceScorer;
+    assert_eq!(scorer.answer_sensitivity(&a, &b, 0), Ok(1.0));
+    assert_eq!(scorer.answer_sensitivity(&a, &b, 1), Ok(-1.0));
+    assert_eq!(scorer.answer_sensitivity(&a, &b, 2), Ok(1.0));
+    assert_eq!(scorer.answer_sensitivity(&a, &b, 3), Ok(0.0));
+}
+
+#[test]
+fn weighted_sensitivity_is_the_signed_weight() {
+    let a = user("a", &[5, 2, 4]);
+    let b = user("b", &[3, 6, 4]);
+    let scorer = WeightedScorer::new(vec![2.0, 0.5, 3.0]).unwrap();
+    assert_eq!(scorer.answer_sensitivity(&a, &b, 0), Ok(2.0));
+    assert_eq!(scorer.answer_sensitivity(&a, &b, 1), Ok(-0.5));
+    assert_eq!(scorer.answer_sensitivity(&a, &b, 2), Ok(3.0));
+}
+
+#[test]
+fn default_sensitivity_agrees_with_closed_forms() {
+    let a = user("a", &[5, 2, 4]);
+    let b = user("b", &[3, 6, 4]);
+    // MeanDifference only has the numeric default: one step changes the mean by 1/3.
+    for q in 0..3 {
+        let closed = SimpleDifferenceScorer
+            .answer_sensitivity(&a, &b, q)
+            .unwrap();
+        let numeric = MeanDifference.answer_sensitivity(&a, &b, q).unwrap();
+        assert!((numeric - closed / 3.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn starring_a_question_shifts_the_top_pair() {
+    let target = user("t", &[1, 4])
+        .with_important(vec![false, true])
+        .unwrap();
+    let splits_first = user("x", &[6, 4]);
+    let splits_second = user("y", &[1, 7]);
+    let scorer = ImportanceScorer::default();
+
+    // Unstarred, the first-question rival is further away (5 vs 3).
+    let plain = user("t", &[1, 4]);
+    assert!(
+        scorer.calculate_score(&plain, &splits_first)
+            > scorer.calculate_score(&plain, &splits_second)
+    );
+    // Starring question 2 doubles that gap to 6, overtaking.
+    assert_eq!(scorer.calculate_score(&target, &splits_first), 5.0);
+    assert_eq!(scorer.calculate_score(&target, &splits_second), 6.0);
+    // Either side starring counts.
+    assert_eq!(scorer.calculate_score(&splits_second, &target), 6.0);
+}
+
+#[test]
+fn importance_multiplier_is_validated() {
+    assert!(ImportanceScorer::new(f64::NAN).is_err());
+    assert!(ImportanceScorer::new(-1.0).is_err());
+    assert_eq!(
+        user("a", &[1, 2]).with_important(vec![true]),
+        Err(NemesisError::MismatchedLengths {
+            expected: 2,
+            got: 1
+        })
+    );
+}
+
+#[test]
+fn circular_scale_makes_the_ends_neighbours() {
+    let a = user("a", &[1, 1]);
+    let b = user("b", &[7, 4]);
+    let scorer = CircularDifferenceScorer::default();
+    // 1 and 7 are one step apart around the circle; 1 and 4 are three.
+    assert_eq!(scorer.calculate_score(&a, &b), 4.0);
+    assert!(scorer.calculate_score(&a, &b) < SimpleDifferenceScorer.calculate_score(&a, &b));
+    assert_eq!(scorer.score_bounds(2), (0.0, 6.0));
+}
+
+#[test]
+fn circular_flags_limit_wrapping_to_some_questions() {
+    let a = user("a", &[1, 1]);
+    let b = user("b", &[7, 7]);
+    let scorer = CircularDifferenceScorer::new(7).with_circular_questions(vec![true, false]);
+    assert!(scorer.is_circular(0) && !scorer.is_circular(1));
+    assert_eq!(scorer.calculate_score(&a, &b), 1.0 + 6.0);
+    let none = CircularDifferenceScorer::new(7).with_circular_questions(Vec::new());
+    assert!(!none.is_circular(0));
+    assert_eq!(none.calculate_score(&a, &b), 12.0);
+    assert_eq!(
+        CircularDifferenceScorer::new(0).calculate_score(&a, &b),
+        12.0
+    );
+}
+
+#[test]
+fn scorer_enum_delegates_to_each_variant() {
+    let a = user("a", &[1, 2, 6]);
+    let b = user("b", &[7, 3, 4]);
+    let weighted = WeightedScorer::new(vec![1.0, 3.0, 0.5]).unwrap();
+    let polarization = PolarizationScorer::new(3.0, 2.0, 1.0).unwrap();
+    let cases: Vec<(Scorer, Box<dyn ScoringStrategy>)> = vec![
+        (Scorer::Simple, Box::new(SimpleDifferenceScorer)),
+        (Scorer::Euclidean, Box::new(EuclideanDistanceScorer)),
+        (weighted.clone().into(), Box::new(weighted)),
+        (polarization.into(), Box::new(polarization)),
+    ];
+    for (scorer, expected) in cases {
+        assert_eq!(scorer.name(), expected.name());
+        assert_eq!(
+            scorer.calculate_score(&a, &b),
+            expected.calculate_score(&a, &b)
+        );
+        assert_eq!(
+            scorer.normalized_per_question(&a, &b),
+            expected.normalized_per_question(&a, &b)
+        );
+        assert_eq!(scorer.score_bounds(3), expected.score_bounds(3));
+        assert_eq!(
+            scorer.answer_sensitivity(&a, &b, 1),
+            expected.answer_sensitivity(&a, &b, 1)
+        );
+    }
+}
+
+#[test]
+fn ideal_simple_difference_opponent_takes_the_far_endpoints() {
+    let target = user("t", &[1, 2, 4, 6, 7]);
+    let ideal = ideal_opponent_responses(&target, &SimpleDifferenceScorer, Scale::default());
+    assert_eq!(ideal, vec![7, 7, 7, 1, 1]);
+
+    let five_point = Scale::new(1, 5).unwrap();
+    assert_eq!(
+        ideal_opponent_responses(&user("t", &[2, 4]), &SimpleDifferenceScorer, five_point),
+        vec![5, 1]
+    );
+}
+
+#[test]
+fn ideal_cosine_opponent_points_the_opposite_way() {
+    // Cosine rewards pointing the opposite way from neutral, not raw distance.
+    let target = user("t", &[6, 2, 4]);
+    let ideal = ideal_opponent_responses(&target, &CosineSimilarityScorer, Scale::default());
+    let ideal_user = user("i", &ideal);
+    assert!((CosineSimilarityScorer.calculate_score(&target, &ideal_user) - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn scale_rejects_empty_ranges() {
+    assert_eq!(
+        Scale::new(5, 5),
+        Err(NemesisError::InvalidScale { min: 5, max: 5 })
+    );
+    let scale = Scale::default();
+    assert_eq!((scale.min(), scale.max()), (1, 7));
+    assert!(scale.contains(4) && !scale.contains(8));
+}
+
+#[test]
+fn recording_scorer_logs_exactly_the_pairs_the_matcher_scored() {
+    let users = vec![user("a", &[1, 2]), user("b", &[7, 6]), user("c", &[4, 4])];
+    let recording = RecordingScorer::new(SimpleDifferenceScorer);
+    let matcher = NemesisMatcher::new(&recording);
+    let pairs = matcher.calculate_all_pairs(&users);
+
+    let expected: Vec<(String, String, f64)> = pairs
+        .iter()
+        .map(|&(i, j, s)| (users[i].id.clone(), users[j].id.clone(), s))
+        .collect();
+    assert_eq!(&*recording.records(), expected.as_slice());
+    assert_eq!(recording.name(), "SimpleDifference");
+
+    assert_eq!(recording.take_records().len(), 3);
+    assert!(recording.records().is_empty());
+}
+
+#[test]
+fn balanced_weights_equalize_question_influence() {
+    let users = vec![
+        user("a", &[1, 3, 4]),
+        user("b", &[7, 4, 4]),
+        user("c", &[2, 5, 4]),
+        user("d", &[6, 4, 4]),
+        user("e", &[1, 3, 4]),
+    ];
+    let balanced = WeightedScorer::balanced_for(&users);
+    // The last question has no spread and must not blow up.
+    assert_eq!(balanced.weights()[2], 0.0);
+
+    let mut per_question = [0.0; 2];
+    let mut pairs = 0.0;
+    for i in 0..users.len() {
+        for j in (i + 1)..users.len() {
+            for (q, total) in per_question.iter_mut().enumerate() {
+                let gap = (users[i].responses[q] - users[j].responses[q]).abs() as f64;
+                *total += gap * balanced.weights()[q];
+            }
+            pairs += 1.0;
+        }
+    }
+    for total in per_question {
+        assert!(
+            (total / pairs - 1.0).abs() < 1e-9,
+            "mean contribution {}",
+            total / pairs
+        );
+    }
+    assert!(WeightedScorer::balanced_for(&[]).weights().is_empty());
+}
+
+#[test]
+fn decomposability_is_reported_per_scorer() {
+    let weighted = WeightedScorer::new(vec![1.0]).unwrap();
+    let decomposable: Vec<Box<dyn ScoringStrategy>> = vec![
+        Box::new(SimpleDifferenceScorer),
+        Box::new(weighted.clone()),
+        Box::new(PolarizationScorer::default()),
+        Box::new(ConfidenceScorer::default()),
+        Box::new(ImportanceScorer::default()),
+        Box::new(CircularDifferenceScorer::default()),
+        Box::new(CachedScorer::new(SimpleDifferenceScorer)),
+        Box::new(Scorer::Weighted(weighted)),
+    ];
+    for scorer in &decomposable {
+        assert!(scorer.is_decomposable(), "{}", scorer.name());
+    }
+    let holistic: Vec<Box<dyn ScoringStrategy>> = vec![
+        Box::new(EuclideanDistanceScorer),
+        Box::new(CosineSimilarityScorer),
+        Box::new(ClampScorer::new(SimpleDifferenceScorer, 5.0)),
+        Box::new(Scorer::Euclidean),
+    ];
+    for scorer in &holistic {
+        assert!(!scorer.is_decomposable(), "{}", scorer.name());
+    }
+}
+
+#[test]
+fn breakdown_of_a_non_decomposable_scorer_is_an_error() {
+    let a = user("a", &[1, 4]);
+    let b = user("b", &[7, 2]);
+    assert_eq!(
+        score_breakdown(&EuclideanDistanceScorer, &a, &b),
+        Err(NemesisError::NotDecomposable(
+            "EuclideanDistance".to_string()
+        ))
+    );
+}
+
+#[test]
+fn shared_conviction_rewards_aligned_extremists() {
+    let scorer = SharedConvictionScorer;
+    let extremist = user("x", &[1, 7, 1, 7]);
+    let ally = user("y", &[1, 7, 1, 1]);
+    let moderate = user("m", &[2, 6, 4, 4]);
+    assert_eq!(scorer.calculate_score(&extremist, &ally), 3.0);
+    assert_eq!(scorer.calculate_score(&extremist, &moderate), 0.0);
+    // Shared neutral answers are not conviction.
+    assert_eq!(scorer.calculate_score(&moderate, &moderate), 0.0);
+}
+
+#[test]
+fn shared_conviction_matcher_pairs_allies() {
+    let users = vec![
+        user("a", &[1, 1, 7]),
+        user("b", &[7, 7, 1]),
+        user("c", &[1, 1, 7]),
+        user("d", &[7, 7, 1]),
+    ];
+    let matches = NemesisMatcher::new(SharedConvictionScorer)
+        .find_matches(&users)
+        .unwrap();
+    let pairs: Vec<(&str, &str)> = matches
+        .iter()
+        .map(|m| (m.user1_id.as_str(), m.user2_id.as_str()))
+        .collect();
+    assert_eq!(pairs, vec![("a", "c"), ("b", "d")]);
+}
+
+#[test]
+fn sparse_weights_fill_in_the_default() {
+    let scorer = WeightedScorer::from_sparse(10, &[(2, 3.0), (7, 0.5)], 1.0).unwrap();
+    let mut expected = vec![1.0; 10];
+    expected[2] = 3.0;
+    expected[7] = 0.5;
+    assert_eq!(scorer.weights(), expected.as_slice());
+
+    let only_two = WeightedScorer::from_sparse(10, &[(2, 3.0), (7, 0.5)], 0.0).unwrap();
+    let a = user("a", &[1; 10]);
+    let b = user("b", &[7; 10]);
+    assert_eq!(only_two.calculate_score(&a, &b), 6.0 * 3.5);
+}
+
+#[test]
+fn sparse_weights_reject_bad_overrides() {
+    assert!(matches!(
+        WeightedScorer::from_sparse(10, &[(10, 1.0)], 1.0),
+        Err(NemesisError::InvalidWeights(_))
+    ));
+    assert!(matches!(
+        WeightedScorer::from_sparse(10, &[(3, -1.0)], 1.0),
+        Err(NemesisError::InvalidWeights(_))
+    ));
+    assert!(matches!(
+        WeightedScorer::from_sparse(10, &[], f64::NAN),
+        Err(NemesisError::InvalidWeights(_))
+    ));
+}
+
+#[test]
+fn table_conviction_reproduces_default_polarization() {
+    let table = TableConvictionScorer::new([2.0, 1.5, 1.0, 1.0, 1.0, 1.5, 2.0]).unwrap();
+    let polarization = PolarizationScorer::default();
+    for (a, b) in random_users(20, 8, 3)
+        .iter()
+        .zip(random_users(20, 8, 4).iter())
+    {
+        assert_eq!(
+            table.calculate_score(a, b),
+            polarization.calculate_score(a, b)
+        );
+    }
+    assert_eq!(table.score_bounds(8), polarization.score_bounds(8));
+}
+
+#[test]
+fn table_conviction_rejects_negative_entries() {
+    assert!(matches!(
+        TableConvictionScorer::new([1.0, 1.0, -1.0, 1.0, 1.0, 1.0, 1.0]),
+        Err(NemesisError::InvalidWeights(_))
+    ));
+}
+
+#[test]
+fn pool_nemesis_profile_answers_against_the_lean() {
+    let pool = vec![
+        user("a", &[1, 2, 6, 4]),
+        user("b", &[2, 1, 7, 3]),
+        user("c", &[3, 2, 6, 2]),
+    ];
+    assert_eq!(
+        pool_nemesis_profile(&pool, &SimpleDifferenceScorer, Scale::default()),
+        vec![7, 7, 1, 7]
+    );
+    assert_eq!(
+        pool_nemesis_profile(&pool, &SimpleDifferenceScorer, Scale::new(1, 5).unwrap()),
+        vec![5, 5, 1, 5]
+    );
+    assert!(pool_nemesis_profile(&[], &SimpleDifferenceScorer, Scale::default()).is_empty());
+}
+
+#[test]
+fn weighted_scores_within_bounds_pass_the_checked_path() {
+    let scorer = WeightedScorer::new(vec![0.1, 3.0, 1e6]).unwrap();
+    let a = user("a", &[1, 7, 1]);
+    let b = user("b", &[7, 1, 7]);
+    let max = scorer.score_bounds(3).1;
+    assert_eq!(scorer.try_calculate_score(&a, &b), Ok(max));
+}
+
+#[test]
+fn weighted_scores_above_bounds_are_rejected() {
+    // Deserializing directly skips response validation.
+    let off_scale: User = serde_json::from_str(r#"{"id": "bad", "responses": [1, 40]}"#).unwrap();
+    let scorer = WeightedScorer::new(vec![1.0, 1.0]).unwrap();
+    match scorer.try_calculate_score(&off_scale, &user("b", &[7, 1])) {
+        Err(NemesisError::ScoreOutOfBounds { score, max, .. }) => {
+            assert_eq!(score, 45.0);
+            assert_eq!(max, 12.0);
+        }
+        other => panic!("expected ScoreOutOfBounds, got {other:?}"),
+    }
+}
+
+#[test]
+fn wrappers_forward_the_checked_weighted_path() {
+    let off_scale: User = serde_json::from_str(r#"{"id": "bad", "responses": [1, 40]}"#).unwrap();
+    let b = user("b", &[7, 1]);
+    let weighted = WeightedScorer::new(vec![1.0, 1.0]).unwrap();
+    let out_of_bounds = |result: Result<f64, NemesisError>| {
+        matches!(result, Err(NemesisError::ScoreOutOfBounds { .. }))
+    };
+    assert!(out_of_bounds(
+        Scorer::from(weighted.clone()).try_calculate_score(&off_scale, &b)
+    ));
+    assert!(out_of_bounds(
+        CachedScorer::new(weighted.clone()).try_calculate_score(&off_scale, &b)
+    ));
+    let recording = RecordingScorer::new(weighted);
+    assert!(out_of_bounds(recording.try_calculate_score(&off_scale, &b)));
+    assert_eq!(recording.try_calculate_score(&b, &b), Ok(0.0));
+    assert_eq!(recording.records().len(), 1);
+}
+
+#[test]
+fn per_question_cap_stops_one_question_dominating() {
+    let scorer = WeightedScorer::new(vec![10.0, 1.0, 1.0]).unwrap();
+    let hub = user("hub", &[4, 4, 4]);
+    // Disagrees only on the heavy question.
+    let one_topic = user("one", &[7, 4, 4]);
+    // Disagrees as much, but across the two ordinary questions.
+    let broad = user("broad", &[4, 7, 1]);
+    assert_eq!(scorer.calculate_score(&hub, &one_topic), 30.0);
+    assert_eq!(scorer.calculate_score(&hub, &broad), 6.0);
+
+    let capped = CapPerQuestion::new(scorer, 3.0).unwrap();
+    assert_eq!(capped.calculate_score(&hub, &one_topic), 3.0);
+    assert_eq!(capped.calculate_score(&hub, &broad), 6.0);
+    assert!(capped.is_decomposable());
+    assert_eq!(capped.score_bounds(3), (0.0, 9.0));
+    assert_eq!(capped.cap(), 3.0);
+
+    for bad in [-1.0, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            CapPerQuestion::new(SimpleDifferenceScorer, bad),
+            Err(NemesisError::InvalidWeights(_))
+        ));
+    }
+    assert!(matches!(
+        CapPerQuestion::new(EuclideanDistanceScorer, 3.0),
+        Err(NemesisError::NotDecomposable(_))
+    ));
+}
+
+#[test]
+fn zscore_matches_relative_patterns_across_ranges() {
+    let wide = user("wide", &[1, 4, 7, 4]);
+    let narrow = user("narrow", &[3, 4, 5, 4]);
+    let flipped = user("flipped", &[7, 4, 1, 4]);
+    let scorer = ZScoreScorer;
+    assert!(scorer.calculate_score(&wide, &narrow) < 1e-12);
+    assert!(EuclideanDistanceScorer.calculate_score(&wide, &narrow) > 2.0);
+    // Exact opposites are as far apart as standardized vectors get.
+    assert!((scorer.calculate_score(&wide, &flipped) - 4.0).abs() < 1e-12);
+    assert_eq!(scorer.score_bounds(4), (0.0, 4.0));
+}
+
+#[test]
+fn zscore_treats_flat_answers_as_no_opinion() {
+    let flat = user("flat", &[4, 4, 4, 4]);
+    let also_flat = user("also", &[7, 7, 7, 7]);
+    assert_eq!(ZScoreScorer::standardize(&flat), vec![0.0; 4]);
+    assert_eq!(ZScoreScorer.calculate_score(&flat, &also_flat), 0.0);
+    let varied = user("varied", &[1, 4, 7, 4]);
+    assert!((ZScoreScorer.calculate_score(&flat, &varied) - 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn mirrored_extremist_is_maximally_opposed() {
+    let scale = Scale::default();
+    let all_low = user("low", &[1; 5]);
+    let max = SimpleDifferenceScorer.score_bounds(5).1;
+    assert_eq!(
+        self_mirror_opposition(&all_low, &SimpleDifferenceScorer, scale),
+        max
+    );
+    assert_eq!(
+        self_mirror_opposition(&user("mid", &[4; 5]), &SimpleDifferenceScorer, scale),
+        0.0
+    );
+    assert_eq!(scale.mirror(2), 6);
+
+    let wide = Scale::new(i32::MAX - 1, i32::MAX).unwrap();
+    assert_eq!(wide.mirror(i32::MAX), i32::MAX - 1);
+    assert_eq!(wide.mirror(0), i32::MAX);
+}
+
+#[test]
+fn response_rate_weights_discount_mostly_skipped_questions() {
+    let partial = |id: &str, r: &[Option<i32>]| PartialUser::new(id, r.to_vec()).unwrap();
+    let pool = vec![
+        partial("a", &[Some(1), Some(1), None]),
+        partial("b", &[Some(7), Some(7), None]),
+        partial("c", &[Some(2), None, None]),
+        partial("d", &[Some(6), Some(4), Some(7)]),
+    ];
+    let scorer = WeightedScorer::by_response_rate(&pool);
+    assert_eq!(scorer.weights(), &[1.0, 0.75, 0.25]);
+
+    let mut pool = pool;
+    let users = impute_missing(&mut pool, Imputation::Neutral).unwrap();
+    // a–d clash fully on the last question, but only d answered it.
+    assert_eq!(
+        scorer.calculate_score(&users[0], &users[3]),
+        5.0 + 2.25 + 0.75
+    );
+}
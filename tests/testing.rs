@@ -0,0 +1,30 @@
+#![cfg(feature = "test-util")]
+
+use nemesis_finder::testing::random_users;
+use nemesis_finder::user::validate_responses;
+
+#[test]
+fn same_seed_yields_identical_users() {
+    assert_eq!(random_users(20, 25, 42), random_users(20, 25, 42));
+    assert_ne!(random_users(20, 25, 42), random_users(20, 25, 43));
+}
+
+#[test]
+fn generated_users_are_valid() {
+    let users = random_users(50, 10, 7);
+    assert_eq!(users.len(), 50);
+    for user in &users {
+        assert_eq!(user.responses.len(), 10);
+        assert_eq!(validate_responses(&user.responses), Ok(()));
+    }
+    // Every answer on the scale shows up in a pool this size.
+    for value in 1..=7 {
+        assert!(users.iter().any(|u| u.responses.contains(&value)));
+    }
+}
+
+#[test]
+#[should_panic(expected = "at least one question")]
+fn zero_questions_cannot_make_valid_users() {
+    random_users(1, 0, 7);
+}
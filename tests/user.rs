@@ -0,0 +1,311 @@
+mod common;
+
+use common::user;
+use nemesis_finder::packed::PackedUser;
+use nemesis_finder::user::{
+    dedupe_users, detect_scale, impute_missing, migrate_user, validate_pool, DedupePolicy,
+    Imputation, PartialUser, CURRENT_SCHEMA_VERSION,
+};
+use nemesis_finder::{NemesisError, Scale, User};
+use serde_json::json;
+
+#[test]
+fn v1_payload_without_version_loads_and_upgrades() {
+    let payload = json!({ "id": "legacy", "responses": [1, 4, 7] });
+
+    let raw: User = serde_json::from_value(payload.clone()).unwrap();
+    assert_eq!(raw.schema_version, 1);
+
+    let migrated = migrate_user(payload).unwrap();
+    assert_eq!(migrated.id, "legacy");
+    assert_eq!(migrated.responses, vec![1, 4, 7]);
+    assert_eq!(migrated.schema_version, CURRENT_SCHEMA_VERSION);
+}
+
+#[test]
+fn new_users_serialize_the_current_version() {
+    let value = serde_json::to_value(user("a", &[2, 3])).unwrap();
+    assert_eq!(value["schema_version"], json!(CURRENT_SCHEMA_VERSION));
+    assert_eq!(migrate_user(value).unwrap(), user("a", &[2, 3]));
+}
+
+#[test]
+fn migration_rejects_future_versions_and_bad_payloads() {
+    let future =
+        json!({ "id": "x", "responses": [1], "schema_version": CURRENT_SCHEMA_VERSION + 1 });
+    assert_eq!(
+        migrate_user(future),
+        Err(NemesisError::UnsupportedSchemaVersion(
+            CURRENT_SCHEMA_VERSION + 1
+        ))
+    );
+    assert!(matches!(
+        migrate_user(json!({ "id": "x" })),
+        Err(NemesisError::Deserialization(_))
+    ));
+    assert_eq!(
+        migrate_user(json!({ "id": "x", "responses": [9] })),
+        Err(NemesisError::InvalidResponse { index: 0, value: 9 })
+    );
+}
+
+fn duplicated_pool() -> Vec<User> {
+    vec![
+        user("dup", &[1, 2, 7]),
+        user("solo", &[4, 4, 4]),
+        user("dup", &[2, 3, 6]),
+    ]
+}
+
+#[test]
+fn dedupe_keep_first() {
+    let users = dedupe_users(duplicated_pool(), DedupePolicy::KeepFirst);
+    assert_eq!(
+        users,
+        vec![user("dup", &[1, 2, 7]), user("solo", &[4, 4, 4])]
+    );
+}
+
+#[test]
+fn dedupe_keep_last() {
+    let users = dedupe_users(duplicated_pool(), DedupePolicy::KeepLast);
+    assert_eq!(
+        users,
+        vec![user("dup", &[2, 3, 6]), user("solo", &[4, 4, 4])]
+    );
+}
+
+#[test]
+fn dedupe_average_rounds_to_valid_answers() {
+    let users = dedupe_users(duplicated_pool(), DedupePolicy::Average);
+    // 1.5 -> 2, 2.5 -> 3, 6.5 -> 7 (halves round away from zero).
+    assert_eq!(
+        users,
+        vec![user("dup", &[2, 3, 7]), user("solo", &[4, 4, 4])]
+    );
+}
+
+#[test]
+fn dedupe_average_skips_mismatched_lengths() {
+    let users = vec![
+        user("dup", &[1, 2]),
+        user("dup", &[1, 2, 3]),
+        user("solo", &[5]),
+    ];
+    assert_eq!(
+        dedupe_users(users.clone(), DedupePolicy::Average),
+        vec![user("solo", &[5])]
+    );
+    assert_eq!(dedupe_users(users, DedupePolicy::KeepFirst).len(), 2);
+}
+
+fn partial_pool() -> Vec<PartialUser> {
+    vec![
+        PartialUser::new("a", vec![Some(1), None, Some(2)]).unwrap(),
+        PartialUser::new("b", vec![Some(6), Some(7), None]).unwrap(),
+        PartialUser::new("c", vec![None, Some(6), Some(7)]).unwrap(),
+    ]
+}
+
+#[test]
+fn impute_population_mean() {
+    let mut users = partial_pool();
+    let complete = impute_missing(&mut users, Imputation::PopulationMean).unwrap();
+    // Question means: (1+6)/2 = 3.5 -> 4, (7+6)/2 = 6.5 -> 7, (2+7)/2 = 4.5 -> 5.
+    assert_eq!(complete[0], user("a", &[1, 7, 2]));
+    assert_eq!(complete[1], user("b", &[6, 7, 5]));
+    assert_eq!(complete[2], user("c", &[4, 6, 7]));
+    assert!(users
+        .iter()
+        .all(|u| u.responses.iter().all(Option::is_some)));
+}
+
+#[test]
+fn impute_neutral() {
+    let mut users = partial_pool();
+    let complete = impute_missing(&mut users, Imputation::Neutral).unwrap();
+    assert_eq!(complete[0], user("a", &[1, 4, 2]));
+    assert_eq!(complete[1], user("b", &[6, 7, 4]));
+    assert_eq!(complete[2], user("c", &[4, 6, 7]));
+}
+
+#[test]
+fn impute_per_user_mean() {
+    let mut users = partial_pool();
+    let complete = impute_missing(&mut users, Imputation::PerUserMean).unwrap();
+    // a: (1+2)/2 = 1.5 -> 2, b: 6.5 -> 7, c: 6.5 -> 7.
+    assert_eq!(complete[0], user("a", &[1, 2, 2]));
+    assert_eq!(complete[1], user("b", &[6, 7, 7]));
+    assert_eq!(complete[2], user("c", &[7, 6, 7]));
+}
+
+#[test]
+fn impute_falls_back_to_neutral_without_data() {
+    let mut users = vec![PartialUser::new("blank", vec![None, None]).unwrap()];
+    for strategy in [
+        Imputation::PopulationMean,
+        Imputation::Neutral,
+        Imputation::PerUserMean,
+    ] {
+        let complete = impute_missing(&mut users.clone(), strategy).unwrap();
+        assert_eq!(complete, vec![user("blank", &[4, 4])]);
+    }
+    assert_eq!(
+        PartialUser::new("x", vec![Some(0)]),
+        Err(NemesisError::InvalidResponse { index: 0, value: 0 })
+    );
+    impute_missing(&mut users, Imputation::Neutral).unwrap();
+    assert_eq!(users[0].responses, vec![Some(4), Some(4)]);
+}
+
+#[test]
+fn tags_default_to_empty_and_round_trip() {
+    let legacy: User = serde_json::from_value(json!({ "id": "a", "responses": [1] })).unwrap();
+    assert!(legacy.tags.is_empty());
+
+    let tagged = user("a", &[1]).with_tag("region", "north");
+    let value = serde_json::to_value(&tagged).unwrap();
+    assert_eq!(value["tags"], json!({ "region": "north" }));
+    let restored: User = serde_json::from_value(value).unwrap();
+    assert_eq!(restored.tag("region"), Some("north"));
+    assert!(serde_json::to_value(user("b", &[1]))
+        .unwrap()
+        .get("tags")
+        .is_none());
+}
+
+fn invalid(id: &str, error: NemesisError) -> NemesisError {
+    NemesisError::InvalidUser {
+        id: id.to_string(),
+        error: Box::new(error),
+    }
+}
+
+#[test]
+fn validate_pool_accepts_clean_pool() {
+    let users = vec![user("a", &[1, 2]), user("b", &[7, 6])];
+    assert_eq!(validate_pool(&users), Ok(()));
+    assert_eq!(validate_pool(&[]), Ok(()));
+}
+
+#[test]
+fn validate_pool_reports_every_problem() {
+    let mut out_of_range = user("c", &[1, 1]);
+    out_of_range.responses = vec![0, 9];
+    let users = vec![
+        user("a", &[1, 2]),
+        user("a", &[3, 4]),
+        user("b", &[1, 2, 3]),
+        out_of_range,
+        user("a", &[5, 6]),
+    ];
+    let errors = validate_pool(&users).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            NemesisError::DuplicateUserId("a".to_string()),
+            invalid(
+                "b",
+                NemesisError::MismatchedLengths {
+                    expected: 2,
+                    got: 3
+                }
+            ),
+            invalid("c", NemesisError::InvalidResponse { index: 0, value: 0 }),
+            invalid("c", NemesisError::InvalidResponse { index: 1, value: 9 }),
+        ]
+    );
+    assert_eq!(
+        errors[1].to_string(),
+        "user \"b\": expected 2 responses, got 3"
+    );
+}
+
+#[test]
+fn new_rejects_empty_ids() {
+    assert_eq!(
+        User::new("", vec![4]),
+        Err(NemesisError::InvalidId(String::new()))
+    );
+}
+
+#[test]
+fn new_rejects_whitespace_ids_and_trims_padding() {
+    assert_eq!(
+        User::new(" \t\n", vec![4]),
+        Err(NemesisError::InvalidId(" \t\n".to_string()))
+    );
+    assert_eq!(User::new("  alice ", vec![4]).unwrap().id, "alice");
+}
+
+#[test]
+fn every_entry_point_checks_ids() {
+    let blank = || NemesisError::InvalidId("  ".to_string());
+    assert_eq!(PartialUser::new("  ", vec![Some(4)]), Err(blank()));
+    assert_eq!(PartialUser::new(" a ", vec![Some(4)]).unwrap().id, "a");
+
+    let mut partial = vec![PartialUser::new("a", vec![None]).unwrap()];
+    partial[0].id = "  ".to_string();
+    assert_eq!(
+        impute_missing(&mut partial, Imputation::Neutral),
+        Err(blank())
+    );
+    assert_eq!(partial[0].responses, vec![None]);
+
+    let mut packed = PackedUser::pack(&user("a", &[4])).unwrap();
+    packed.id = "  ".to_string();
+    assert_eq!(packed.unpack(), Err(blank()));
+
+    let json = json!({"id": "  ", "responses": [4]});
+    assert!(serde_json::from_value::<User>(json.clone()).is_err());
+    assert!(matches!(
+        migrate_user(json),
+        Err(NemesisError::Deserialization(_))
+    ));
+    let padded: User = serde_json::from_str(r#"{"id": " a ", "responses": [4]}"#).unwrap();
+    assert_eq!(padded.id, "a");
+}
+
+#[test]
+fn detect_scale_uses_observed_endpoints() {
+    let users = vec![user("a", &[1, 3, 5]), user("b", &[2, 4, 4])];
+    assert_eq!(detect_scale(&users), Scale::new(1, 5).unwrap());
+    assert_eq!(detect_scale(&[user("c", &[4, 4])]), Scale::default());
+    assert_eq!(detect_scale(&[]), Scale::default());
+}
+
+#[test]
+fn user_converts_from_id_and_responses() {
+    let converted = User::try_from(("a", vec![1, 4, 7])).unwrap();
+    assert_eq!(converted, User::new("a", vec![1, 4, 7]).unwrap());
+    assert_eq!(
+        User::try_from(("b", vec![1, 9])),
+        Err(NemesisError::InvalidResponse { index: 1, value: 9 })
+    );
+}
+
+#[test]
+fn partial_user_conversions_round_trip() {
+    let original = User::new("a", vec![2, 6]).unwrap();
+    let partial = PartialUser::from(original.clone());
+    assert_eq!(partial.responses, vec![Some(2), Some(6)]);
+    assert_eq!(User::try_from(partial).unwrap(), original);
+
+    let tagged = User::new("t", vec![3, 5])
+        .unwrap()
+        .with_tag("region", "north")
+        .with_confidence(vec![0.5, 1.0])
+        .unwrap()
+        .with_important(vec![true, false])
+        .unwrap();
+    let partial = PartialUser::from(tagged.clone());
+    assert_eq!(User::try_from(partial.clone()).unwrap(), tagged);
+    let imputed = impute_missing(&mut [partial], Imputation::Neutral).unwrap();
+    assert_eq!(imputed, vec![tagged]);
+
+    let skipped = PartialUser::new("b", vec![Some(1), None]).unwrap();
+    assert_eq!(
+        User::try_from(skipped),
+        Err(NemesisError::MissingResponse { index: 1 })
+    );
+}